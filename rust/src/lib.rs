@@ -1,9 +1,53 @@
 // FFI wrapper for loqa-voice-dsp crate
 // Provides C-compatible exports for iOS (Swift FFI) and Android (Kotlin JNI)
 
-use std::os::raw::{c_float, c_int};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::os::raw::{c_char, c_float, c_int};
 use std::slice;
 
+/// Applies a window function to `samples` in place before a transform.
+///
+/// `window_type` follows the JNI convention shared with `nativeComputeFFT`:
+/// 0 = none, 1 = Hann, 2 = Hamming, 3 = Blackman. Unknown values are treated
+/// as `none` and logged, since an invalid window shouldn't be fatal to an
+/// otherwise-valid FFT request.
+fn apply_window(samples: &mut [f32], window_type: c_int) {
+    let len = samples.len();
+    if len < 2 {
+        return;
+    }
+
+    let n_minus_1 = (len - 1) as f32;
+    match window_type {
+        0 => {}
+        1 => {
+            for (n, sample) in samples.iter_mut().enumerate() {
+                let w = 0.5 * (1.0 - (2.0 * PI * n as f32 / n_minus_1).cos());
+                *sample *= w;
+            }
+        }
+        2 => {
+            for (n, sample) in samples.iter_mut().enumerate() {
+                let w = 0.54 - 0.46 * (2.0 * PI * n as f32 / n_minus_1).cos();
+                *sample *= w;
+            }
+        }
+        3 => {
+            for (n, sample) in samples.iter_mut().enumerate() {
+                let w = 0.42 - 0.5 * (2.0 * PI * n as f32 / n_minus_1).cos()
+                    + 0.08 * (4.0 * PI * n as f32 / n_minus_1).cos();
+                *sample *= w;
+            }
+        }
+        _ => {
+            eprintln!(
+                "[Rust FFI] Warning: unknown window_type {window_type}, defaulting to none"
+            );
+        }
+    }
+}
+
 /// Computes Fast Fourier Transform (FFT) of audio buffer
 ///
 /// # Arguments
@@ -11,6 +55,8 @@ use std::slice;
 /// * `length` - Number of samples in input buffer
 /// * `sample_rate` - Sample rate in Hz (e.g., 44100, 48000)
 /// * `fft_size` - FFT size (must be power of 2, range: 256-8192)
+/// * `window_type` - Window function applied before the transform: 0=none,
+///   1=Hann, 2=Hamming, 3=Blackman. Unknown values fall back to none.
 ///
 /// # Returns
 /// * Pointer to magnitude spectrum (length = fft_size / 2 + 1) or null on error
@@ -26,15 +72,16 @@ use std::slice;
 /// * Rust allocates → Returns raw pointer → Swift/Kotlin copies → Swift/Kotlin frees Rust memory
 ///
 /// # Note
-/// The loqa-voice-dsp crate applies its own windowing internally, so we don't expose
-/// window type as a parameter in this FFI interface. The TypeScript layer may accept
-/// window type as an option, but it will be handled at that layer for v0.1.0.
+/// Windowing is applied here rather than inside loqa-voice-dsp, since the right
+/// choice of window depends on what the caller is analyzing (transient detection
+/// wants different leakage/resolution tradeoffs than tonal peak measurement).
 #[no_mangle]
 pub unsafe extern "C" fn compute_fft_rust(
     buffer: *const c_float,
     length: c_int,
     sample_rate: c_int,
     fft_size: c_int,
+    window_type: c_int,
 ) -> *mut c_float {
     // Input validation
     if buffer.is_null() {
@@ -69,9 +116,13 @@ pub unsafe extern "C" fn compute_fft_rust(
     // Convert raw pointer to Rust slice
     let input_slice = slice::from_raw_parts(buffer, length as usize);
 
+    // Apply the requested window function to a scratch copy before transforming;
+    // the input buffer itself must not be mutated since it's caller-owned.
+    let mut windowed: Vec<f32> = input_slice.to_vec();
+    apply_window(&mut windowed, window_type);
+
     // Call loqa-voice-dsp FFT function
-    let fft_result =
-        loqa_voice_dsp::compute_fft(input_slice, sample_rate as u32, fft_size_usize);
+    let fft_result = loqa_voice_dsp::compute_fft(&windowed, sample_rate as u32, fft_size_usize);
 
     // Handle FFT computation result
     let magnitudes = match fft_result {
@@ -137,7 +188,7 @@ pub unsafe extern "C" fn free_fft_result_rust(ptr: *mut c_float, length: c_int)
 /// * `class` - JNI class reference (unused but required by JNI)
 /// * `buffer` - JNI jfloatArray reference to input audio samples
 /// * `fft_size` - FFT size (must be power of 2, range: 256-8192)
-/// * `window_type` - Window function type (0=none, 1=hanning, 2=hamming, 3=blackman) - IGNORED in v0.1.0
+/// * `window_type` - Window function type (0=none, 1=hanning, 2=hamming, 3=blackman)
 ///
 /// # Returns
 /// * JNI jfloatArray containing magnitude spectrum (length = fft_size / 2 + 1) or null on error
@@ -147,7 +198,6 @@ pub unsafe extern "C" fn free_fft_result_rust(ptr: *mut c_float, length: c_int)
 /// * This function is called from Kotlin via JNI, not directly
 ///
 /// # Note
-/// For v0.1.0, window_type is accepted but ignored - loqa-voice-dsp applies windowing internally.
 /// Sample rate is hardcoded to 44100 Hz (matches default in LoqaAudioDspModule.kt).
 /// This function delegates to compute_fft_rust with appropriate parameters.
 ///
@@ -162,7 +212,7 @@ pub unsafe extern "C" fn Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativ
     buffer: *const c_float,
     buffer_length: c_int,
     fft_size: c_int,
-    _window_type: c_int,  // Accepted but ignored - windowing handled by loqa-voice-dsp
+    window_type: c_int,
 ) -> *mut c_float {
     // Use default sample rate (44100 Hz) for Android in v0.1.0
     // Matches the default in LoqaAudioDspModule.kt
@@ -170,24 +220,306 @@ pub unsafe extern "C" fn Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativ
 
     // Delegate to the main FFT implementation
     // The JNI framework handles conversion of FloatArray to *const f32 and back
-    compute_fft_rust(buffer, buffer_length, DEFAULT_SAMPLE_RATE, fft_size)
+    compute_fft_rust(buffer, buffer_length, DEFAULT_SAMPLE_RATE, fft_size, window_type)
+}
+
+/// Linear band scaling: each band is the mean FFT magnitude of the bins it covers.
+pub const SPECTRUM_SCALING_LINEAR: c_int = 0;
+
+/// "Optimized logarithmic" band scaling: each band is `20*log10(magnitude)`,
+/// clamped to a fixed dB floor and normalized to 0.0..1.0.
+pub const SPECTRUM_SCALING_LOG: c_int = 1;
+
+/// dB floor used to normalize `SPECTRUM_SCALING_LOG` bands into 0.0..1.0.
+/// Magnitudes at or below this level map to 0.0; 0 dB (unity magnitude) maps to 1.0.
+const SPECTRUM_LOG_FLOOR_DB: f32 = -60.0;
+
+/// Lowest edge frequency used when partitioning the spectrum into log-spaced bands.
+const SPECTRUM_MIN_FREQUENCY: f32 = 40.0;
+
+/// Computes a log-spaced graphic-EQ band reduction of an audio buffer's FFT magnitudes
+///
+/// Runs the FFT internally and collapses the `fft_size/2 + 1` magnitude bins into
+/// `num_bands` perceptually spaced bands (e.g. for a 16/32-band GEQ/spectrum display),
+/// so callers don't have to ship the full spectrum across the FFI boundary and rebin
+/// it in JS/Kotlin.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (e.g., 44100, 48000)
+/// * `fft_size` - FFT size (must be power of 2, range: 256-8192)
+/// * `num_bands` - Number of output bands (must be > 0)
+/// * `scaling` - `SPECTRUM_SCALING_LINEAR` (0) or `SPECTRUM_SCALING_LOG` (1)
+/// * `out_bands` - Caller-allocated buffer of at least `num_bands` floats to receive the result
+///
+/// # Returns
+/// * `0` on success, `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * Caller must ensure `out_bands` points to valid, writable memory of at least `num_bands` floats
+/// * This function dereferences raw pointers and is inherently unsafe
+///
+/// # Band Edges
+/// Band edges are spaced logarithmically from `SPECTRUM_MIN_FREQUENCY` (40 Hz) to
+/// Nyquist (`sample_rate / 2`): `edge[i] = f_min * (f_max/f_min)^(i/num_bands)`. Each
+/// band takes the mean magnitude of the FFT bins whose center frequency falls inside it;
+/// bands with no covered bins are reported as 0.0.
+#[no_mangle]
+pub unsafe extern "C" fn compute_spectrum_bands_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    fft_size: c_int,
+    num_bands: c_int,
+    scaling: c_int,
+    out_bands: *mut c_float,
+) -> c_int {
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return -1;
+    }
+
+    if out_bands.is_null() {
+        eprintln!("[Rust FFI] Error: out_bands pointer is null");
+        return -1;
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return -1;
+    }
+
+    if sample_rate <= 0 {
+        eprintln!("[Rust FFI] Error: sample_rate must be > 0, got {sample_rate}");
+        return -1;
+    }
+
+    if num_bands <= 0 {
+        eprintln!("[Rust FFI] Error: num_bands must be > 0, got {num_bands}");
+        return -1;
+    }
+
+    let fft_size_usize = fft_size as usize;
+    if fft_size <= 0 || (fft_size_usize & (fft_size_usize - 1)) != 0 {
+        eprintln!("[Rust FFI] Error: fft_size must be power of 2, got {fft_size}");
+        return -1;
+    }
+
+    if !(256..=8192).contains(&fft_size) {
+        eprintln!("[Rust FFI] Error: fft_size must be in range [256, 8192], got {fft_size}");
+        return -1;
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+
+    let fft_result = loqa_voice_dsp::compute_fft(input_slice, sample_rate as u32, fft_size_usize);
+    let magnitudes = match fft_result {
+        Ok(result) => result.magnitudes,
+        Err(e) => {
+            eprintln!("[Rust FFI] FFT computation failed: {e:?}");
+            return -1;
+        }
+    };
+
+    let num_bands_usize = num_bands as usize;
+    let f_min = SPECTRUM_MIN_FREQUENCY;
+    let f_max = (sample_rate as f32 / 2.0).max(f_min + 1.0);
+    let bin_hz = sample_rate as f32 / fft_size as f32;
+
+    let edge = |i: usize| -> f32 { f_min * (f_max / f_min).powf(i as f32 / num_bands_usize as f32) };
+
+    let out_slice = slice::from_raw_parts_mut(out_bands, num_bands_usize);
+    for (band_index, out_value) in out_slice.iter_mut().enumerate() {
+        let lo = edge(band_index);
+        let hi = edge(band_index + 1);
+
+        let mut sum = 0.0_f32;
+        let mut count = 0u32;
+        for (bin_index, &mag) in magnitudes.iter().enumerate() {
+            let bin_freq = bin_index as f32 * bin_hz;
+            if bin_freq >= lo && bin_freq < hi {
+                sum += mag;
+                count += 1;
+            }
+        }
+
+        let mean_magnitude = if count > 0 { sum / count as f32 } else { 0.0 };
+
+        *out_value = if scaling == SPECTRUM_SCALING_LOG {
+            if mean_magnitude <= 0.0 {
+                0.0
+            } else {
+                let db = 20.0 * mean_magnitude.log10();
+                ((db - SPECTRUM_LOG_FLOOR_DB) / -SPECTRUM_LOG_FLOOR_DB).clamp(0.0, 1.0)
+            }
+        } else {
+            mean_magnitude
+        };
+    }
+
+    0
+}
+
+/// Opaque per-band noise gate for the spectrum/band output
+///
+/// Tracks an envelope-smoothed level per band so that `spectrum_squelch_apply_rust`
+/// can zero out idle/noisy bands without the flicker a naive instantaneous threshold
+/// produces on a live spectrum/GEQ display.
+pub struct SpectrumSquelch {
+    levels: Vec<f32>,
+    attack_coef: f32,
+    decay_coef: f32,
+}
+
+/// Creates a `SpectrumSquelch` handle for `num_bands` bands
+///
+/// # Arguments
+/// * `num_bands` - Number of bands this gate will be applied to (must be > 0)
+/// * `attack_ms` - Time constant for rising levels, in milliseconds (must be > 0)
+/// * `decay_ms` - Time constant for falling levels, in milliseconds (must be > 0)
+/// * `frame_rate` - Frames (calls to `spectrum_squelch_apply_rust`) per second (must be > 0)
+///
+/// # Returns
+/// * Pointer to a new `SpectrumSquelch`, or null on invalid arguments
+///
+/// # Safety
+/// * Caller MUST call `spectrum_squelch_destroy_rust` to free the returned handle
+#[no_mangle]
+pub unsafe extern "C" fn spectrum_squelch_create_rust(
+    num_bands: c_int,
+    attack_ms: c_float,
+    decay_ms: c_float,
+    frame_rate: c_float,
+) -> *mut SpectrumSquelch {
+    if num_bands <= 0 {
+        eprintln!("[Rust FFI] Error: num_bands must be > 0, got {num_bands}");
+        return std::ptr::null_mut();
+    }
+
+    if attack_ms <= 0.0 || decay_ms <= 0.0 || frame_rate <= 0.0 {
+        eprintln!(
+            "[Rust FFI] Error: attack_ms, decay_ms, and frame_rate must be > 0, got {attack_ms}, {decay_ms}, {frame_rate}"
+        );
+        return std::ptr::null_mut();
+    }
+
+    let attack_coef = (-1.0 / (attack_ms * 0.001 * frame_rate)).exp();
+    let decay_coef = (-1.0 / (decay_ms * 0.001 * frame_rate)).exp();
+
+    Box::into_raw(Box::new(SpectrumSquelch {
+        levels: vec![0.0; num_bands as usize],
+        attack_coef,
+        decay_coef,
+    }))
+}
+
+/// Applies the noise gate to a frame of band values in place
+///
+/// Updates the handle's per-band smoothed level (rising bands ease in via
+/// `attack_coef`, falling bands decay via `decay_coef`) and zeroes any band whose
+/// smoothed level stays under `squelch`, rather than gating on the raw, noisy value.
+///
+/// # Arguments
+/// * `handle` - Handle returned by `spectrum_squelch_create_rust`
+/// * `bands` - Band values to gate in place (e.g. the `out_bands` from
+///   `compute_spectrum_bands_rust`); length must match the handle's `num_bands`
+/// * `num_bands` - Number of bands in `bands`
+/// * `squelch` - Noise-floor threshold, in the same units as the band values
+///
+/// # Returns
+/// * `0` on success, `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * `handle` must be a valid, non-destroyed pointer from `spectrum_squelch_create_rust`
+/// * `bands` must point to valid, writable memory of at least `num_bands` floats
+#[no_mangle]
+pub unsafe extern "C" fn spectrum_squelch_apply_rust(
+    handle: *mut SpectrumSquelch,
+    bands: *mut c_float,
+    num_bands: c_int,
+    squelch: c_float,
+) -> c_int {
+    if handle.is_null() {
+        eprintln!("[Rust FFI] Error: handle pointer is null");
+        return -1;
+    }
+
+    if bands.is_null() || num_bands <= 0 {
+        eprintln!("[Rust FFI] Error: bands pointer is null or num_bands <= 0");
+        return -1;
+    }
+
+    let state = &mut *handle;
+    let bands_slice = slice::from_raw_parts_mut(bands, num_bands as usize);
+
+    if bands_slice.len() != state.levels.len() {
+        eprintln!(
+            "[Rust FFI] Error: num_bands {} does not match handle's band count {}",
+            bands_slice.len(),
+            state.levels.len()
+        );
+        return -1;
+    }
+
+    for (value, level) in bands_slice.iter_mut().zip(state.levels.iter_mut()) {
+        let new_value = *value;
+        *level = if new_value > *level {
+            new_value + state.attack_coef * (*level - new_value)
+        } else {
+            (*level * state.decay_coef).max(new_value)
+        };
+
+        if *level < squelch {
+            *value = 0.0;
+        }
+    }
+
+    0
 }
 
+/// Destroys a `SpectrumSquelch` handle created by `spectrum_squelch_create_rust`
+///
+/// # Safety
+/// * Must only be called once per handle
+/// * `handle` must not be used after this call
+/// * Null pointers are handled gracefully and do nothing
+#[no_mangle]
+pub unsafe extern "C" fn spectrum_squelch_destroy_rust(handle: *mut SpectrumSquelch) {
+    if handle.is_null() {
+        return;
+    }
+
+    let _ = Box::from_raw(handle);
+}
+
+/// Default frequency range for pitch detection, suitable for human voice:
+/// 80 Hz (low male) to 400 Hz (high female). Shared by every detection algorithm
+/// (`detect_pitch_rust`, `detect_pitch_bacf_rust`, ...) so their outputs are comparable.
+const PITCH_MIN_FREQUENCY: f32 = 80.0;
+const PITCH_MAX_FREQUENCY: f32 = 400.0;
+
 /// Result structure for pitch detection
 ///
-/// Returns the detected pitch frequency, confidence score, and voicing classification.
+/// Returns the detected pitch frequency, confidence score, voicing classification,
+/// and the nearest equal-tempered MIDI note with its tuning error.
 /// This struct is C-compatible for FFI/JNI interop.
 ///
 /// # Fields
 /// * `frequency` - Detected pitch in Hz (0.0 if unvoiced or no pitch detected)
 /// * `confidence` - Confidence score from 0.0 (low) to 1.0 (high)
 /// * `is_voiced` - Whether the audio segment is voiced (true) or unvoiced (false)
+/// * `midi_note` - Nearest MIDI note number (e.g. 69 = A4), 0 when `is_voiced` is false
+/// * `cents` - Signed tuning error in cents, roughly in `[-50, 50]`, 0 when `is_voiced` is false
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct PitchResult {
     pub frequency: c_float,
     pub confidence: c_float,
     pub is_voiced: bool,
+    pub midi_note: c_int,
+    pub cents: c_int,
 }
 
 /// Detects pitch using YIN algorithm from loqa-voice-dsp crate
@@ -198,7 +530,7 @@ pub struct PitchResult {
 /// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
 ///
 /// # Returns
-/// * PitchResult struct with frequency, confidence, and is_voiced
+/// * PitchResult struct with frequency, confidence, is_voiced, midi_note, and cents
 /// * Returns frequency=0.0, confidence=0.0, is_voiced=false on error
 ///
 /// # Safety
@@ -227,6 +559,8 @@ pub unsafe extern "C" fn detect_pitch_rust(
         frequency: 0.0,
         confidence: 0.0,
         is_voiced: false,
+        midi_note: 0,
+        cents: 0,
     };
 
     // Input validation
@@ -251,32 +585,106 @@ pub unsafe extern "C" fn detect_pitch_rust(
     // Convert raw pointer to Rust slice
     let input_slice = slice::from_raw_parts(buffer, length as usize);
 
-    // Define frequency range for YIN algorithm
-    // Default range suitable for human voice: 80 Hz (low male) to 400 Hz (high female)
-    // Can be extended to 800 Hz for wider coverage
-    const MIN_FREQUENCY: f32 = 80.0;
-    const MAX_FREQUENCY: f32 = 400.0;
+    detect_pitch_on_slice(input_slice, sample_rate as u32)
+}
+
+/// Runs YIN pitch detection on an already-validated sample slice
+///
+/// Shared by `detect_pitch_rust` and the conditioning entry points
+/// (`detect_pitch_filtered_rust`, `detect_pitch_voice_band_rust`) so the
+/// frequency-range defaults and midi/cents conversion stay in one place.
+fn detect_pitch_on_slice(samples: &[f32], sample_rate: u32) -> PitchResult {
+    detect_pitch_on_slice_with_config(samples, sample_rate, PitchConfig::default())
+}
+
+/// Tunable thresholds governing the voicing decision made by the YIN-based detectors
+///
+/// Exposed over FFI so callers can trade off false positives against false negatives
+/// for their own use case (e.g. a whisper detector wants a low `power_threshold`, while
+/// a sung-note tuner wants a strict `clarity_threshold` to reject breathy onsets).
+/// This struct is C-compatible for FFI/JNI interop.
+///
+/// # Fields
+/// * `power_threshold` - Minimum RMS energy of the input required to mark a result voiced
+/// * `clarity_threshold` - Minimum YIN confidence required to mark a result voiced
+/// * `min_frequency` - Lower bound of the search range in Hz
+/// * `max_frequency` - Upper bound of the search range in Hz
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PitchConfig {
+    pub power_threshold: c_float,
+    pub clarity_threshold: c_float,
+    pub min_frequency: c_float,
+    pub max_frequency: c_float,
+}
+
+impl Default for PitchConfig {
+    /// Matches the behavior of `detect_pitch_rust` prior to the introduction of `PitchConfig`:
+    /// no extra power/clarity gating beyond what `loqa_voice_dsp::detect_pitch` already applies,
+    /// searching the standard human-voice range.
+    fn default() -> Self {
+        PitchConfig {
+            power_threshold: 0.0,
+            clarity_threshold: 0.0,
+            min_frequency: PITCH_MIN_FREQUENCY,
+            max_frequency: PITCH_MAX_FREQUENCY,
+        }
+    }
+}
+
+/// Root mean square energy of a sample buffer, used by `detect_pitch_on_slice_with_config`
+/// to gate voicing decisions on `PitchConfig::power_threshold`.
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
+    (sum_sq / samples.len() as f32).sqrt()
+}
+
+fn detect_pitch_on_slice_with_config(
+    samples: &[f32],
+    sample_rate: u32,
+    config: PitchConfig,
+) -> PitchResult {
+    let error_result = PitchResult {
+        frequency: 0.0,
+        confidence: 0.0,
+        is_voiced: false,
+        midi_note: 0,
+        cents: 0,
+    };
 
     // Call loqa-voice-dsp YIN pitch detection function (AC2)
     let pitch_result = loqa_voice_dsp::detect_pitch(
-        input_slice,
-        sample_rate as u32,
-        MIN_FREQUENCY,
-        MAX_FREQUENCY
+        samples,
+        sample_rate,
+        config.min_frequency,
+        config.max_frequency,
     );
 
     // Handle pitch detection result
     match pitch_result {
         Ok(result) => {
             // Extract frequency, confidence, and voiced classification
-            let frequency = if result.is_voiced { result.frequency } else { 0.0 }; // AC4: Return 0.0 if unvoiced
             let confidence = result.confidence.clamp(0.0, 1.0); // Ensure 0.0-1.0 range (AC5)
-            let is_voiced = result.is_voiced;
+            let is_voiced = result.is_voiced
+                && confidence >= config.clarity_threshold
+                && rms(samples) >= config.power_threshold;
+            let frequency = if is_voiced { result.frequency } else { 0.0 }; // AC4: Return 0.0 if unvoiced
+
+            let (midi_note, cents) = if is_voiced && frequency > 0.0 {
+                nearest_midi_and_cents(frequency)
+            } else {
+                (0, 0)
+            };
 
             PitchResult {
                 frequency,
                 confidence,
                 is_voiced,
+                midi_note,
+                cents,
             }
         }
         Err(e) => {
@@ -286,90 +694,1639 @@ pub unsafe extern "C" fn detect_pitch_rust(
     }
 }
 
-/// Android JNI native method for detectPitch
-///
-/// JNI Method Signature Resolution:
-/// - Kotlin declaration: `external fun nativeDetectPitch(buffer: FloatArray, sampleRate: Int): PitchResult`
-/// - Package: com.loqalabs.loqaaudiodsp.RustJNI
-/// - Class: RustBridge (object)
-/// - Method: nativeDetectPitch
-/// - JNI Function Name: Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativeDetectPitch
+/// Returns a `PitchConfig` matching the default, pre-`PitchConfig` voicing behavior
 ///
-/// # Arguments
-/// * `env` - JNI environment pointer (unused but required by JNI)
-/// * `class` - JNI class reference (unused but required by JNI)
-/// * `buffer` - JNI jfloatArray reference to input audio samples
-/// * `buffer_length` - Number of samples in buffer
-/// * `sample_rate` - Sample rate in Hz (8000-48000)
+/// Callers can use this as a starting point and override individual fields before
+/// passing the result to `detect_pitch_with_config_rust`.
+#[no_mangle]
+pub extern "C" fn pitch_config_default_rust() -> PitchConfig {
+    PitchConfig::default()
+}
+
+/// Detects pitch with caller-supplied voicing thresholds and frequency range
 ///
-/// # Returns
-/// * PitchResult struct with frequency, confidence, and is_voiced
+/// Unlike `detect_pitch_rust`, which hard-codes the voicing decision, this entry point
+/// gates `is_voiced` on `config.power_threshold` and `config.clarity_threshold` and
+/// searches `config.min_frequency..config.max_frequency`. Pass `pitch_config_default_rust()`
+/// to reproduce `detect_pitch_rust`'s existing behavior exactly.
 ///
 /// # Safety
-/// * JNI framework ensures proper type conversions and memory management
-/// * This function is called from Kotlin via JNI, not directly
-///
-/// # Note
-/// Unlike FFT, PitchResult is returned by value (small struct), not by pointer.
-/// JNI will automatically marshal this back to Kotlin data class.
+/// `buffer` must point to at least `length` valid `f32` samples.
 #[no_mangle]
-pub unsafe extern "C" fn Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativeDetectPitch(
-    _env: *mut std::os::raw::c_void,
-    _class: *mut std::os::raw::c_void,
+pub unsafe extern "C" fn detect_pitch_with_config_rust(
     buffer: *const c_float,
-    buffer_length: c_int,
+    length: c_int,
     sample_rate: c_int,
+    config: PitchConfig,
 ) -> PitchResult {
-    // Delegate to the main pitch detection implementation
-    // The JNI framework handles conversion of FloatArray to *const f32
-    detect_pitch_rust(buffer, buffer_length, sample_rate)
-}
-
-/// Placeholder FFI function for testing build infrastructure (retained for backward compatibility)
-#[no_mangle]
-pub extern "C" fn test_ffi_bridge() -> i32 {
-    42
-}
+    let error_result = PitchResult {
+        frequency: 0.0,
+        confidence: 0.0,
+        is_voiced: false,
+        midi_note: 0,
+        cents: 0,
+    };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::f32::consts::PI;
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return error_result;
+    }
 
-    #[test]
-    fn test_ffi_placeholder() {
-        assert_eq!(test_ffi_bridge(), 42);
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return error_result;
     }
 
-    #[test]
-    fn test_compute_fft_null_buffer() {
-        unsafe {
-            let result = compute_fft_rust(std::ptr::null(), 1024, 44100, 512);
-            assert!(result.is_null(), "Should return null for null buffer");
-        }
+    if !(8000..=48000).contains(&sample_rate) {
+        eprintln!(
+            "[Rust FFI] Error: sample_rate must be in range [8000, 48000] Hz, got {sample_rate}"
+        );
+        return error_result;
     }
 
-    #[test]
-    fn test_compute_fft_invalid_length() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
-        unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), 0, 44100, 512);
-            assert!(result.is_null(), "Should return null for length <= 0");
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    detect_pitch_on_slice_with_config(input_slice, sample_rate as u32, config)
+}
 
-            let result = compute_fft_rust(buffer.as_ptr(), -10, 44100, 512);
-            assert!(result.is_null(), "Should return null for negative length");
+/// Selects an RBJ cookbook high-pass filter for `design_biquad`
+pub const FILTER_TYPE_HIGHPASS: c_int = 0;
+
+/// Selects an RBJ cookbook low-pass filter for `design_biquad`
+pub const FILTER_TYPE_LOWPASS: c_int = 1;
+
+/// Selects an RBJ cookbook band-pass filter (constant 0 dB peak gain) for `design_biquad`
+pub const FILTER_TYPE_BANDPASS: c_int = 2;
+
+/// Normalized Direct Form I biquad coefficients (`a0` already divided out)
+struct BiquadCoefficients {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+/// Designs an RBJ "Audio EQ Cookbook" biquad
+///
+/// `freq` is clamped to just below Nyquist (`sample_rate / 2`) before use, since a
+/// cutoff at or above Nyquist blows up the naive difference equation into
+/// instability/NaNs. `q` is floored to a small positive value for the same reason.
+fn design_biquad(filter_type: c_int, freq: f32, q: f32, sample_rate: f32) -> BiquadCoefficients {
+    let nyquist = sample_rate * 0.5;
+    let clamped_freq = freq.clamp(1.0, nyquist * 0.99);
+    let clamped_q = q.max(0.1);
+
+    let w0 = 2.0 * PI * clamped_freq / sample_rate;
+    let cos_w0 = w0.cos();
+    let alpha = w0.sin() / (2.0 * clamped_q);
+
+    let (b0, b1, b2, a0, a1, a2) = match filter_type {
+        t if t == FILTER_TYPE_HIGHPASS => (
+            (1.0 + cos_w0) / 2.0,
+            -(1.0 + cos_w0),
+            (1.0 + cos_w0) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos_w0,
+            1.0 - alpha,
+        ),
+        t if t == FILTER_TYPE_BANDPASS => {
+            (alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha)
         }
+        _ => (
+            (1.0 - cos_w0) / 2.0,
+            1.0 - cos_w0,
+            (1.0 - cos_w0) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos_w0,
+            1.0 - alpha,
+        ),
+    };
+
+    BiquadCoefficients {
+        b0: b0 / a0,
+        b1: b1 / a0,
+        b2: b2 / a0,
+        a1: a1 / a0,
+        a2: a2 / a0,
     }
+}
 
-    #[test]
-    fn test_compute_fft_invalid_sample_rate() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
-        unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, 0, 512);
-            assert!(result.is_null(), "Should return null for sample_rate <= 0");
+/// Runs `samples` through a biquad as a Direct Form I difference equation, returning
+/// a new scratch buffer so the caller's input is left untouched.
+fn apply_biquad(coeffs: &BiquadCoefficients, samples: &[f32]) -> Vec<f32> {
+    let mut out = Vec::with_capacity(samples.len());
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0_f32, 0.0_f32, 0.0_f32, 0.0_f32);
 
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, -100, 512);
-            assert!(
+    for &x0 in samples {
+        let y0 = coeffs.b0 * x0 + coeffs.b1 * x1 + coeffs.b2 * x2 - coeffs.a1 * y1 - coeffs.a2 * y2;
+        out.push(y0);
+
+        x2 = x1;
+        x1 = x0;
+        y2 = y1;
+        y1 = y0;
+    }
+
+    out
+}
+
+/// Detects pitch after conditioning the input through an RBJ biquad filter
+///
+/// Lets callers restrict analysis to a frequency band (e.g. the vocal range) so DC,
+/// rumble, and hiss that cause false voicing are filtered out before YIN sees the
+/// signal, rather than having to pre-filter themselves.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
+/// * `filter_type` - `FILTER_TYPE_HIGHPASS` (0), `FILTER_TYPE_LOWPASS` (1), or
+///   `FILTER_TYPE_BANDPASS` (2); unknown values fall back to low-pass
+/// * `freq` - Cutoff (high-pass/low-pass) or center (band-pass) frequency in Hz;
+///   clamped to just below Nyquist
+/// * `q` - Filter Q (resonance/bandwidth); floored to a small positive value
+///
+/// # Returns
+/// * PitchResult struct with frequency, confidence, is_voiced, midi_note, and cents
+/// * Returns frequency=0.0, confidence=0.0, is_voiced=false on error
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * This function dereferences raw pointers and is inherently unsafe
+#[no_mangle]
+pub unsafe extern "C" fn detect_pitch_filtered_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    filter_type: c_int,
+    freq: c_float,
+    q: c_float,
+) -> PitchResult {
+    let error_result = PitchResult {
+        frequency: 0.0,
+        confidence: 0.0,
+        is_voiced: false,
+        midi_note: 0,
+        cents: 0,
+    };
+
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return error_result;
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return error_result;
+    }
+
+    if !(8000..=48000).contains(&sample_rate) {
+        eprintln!(
+            "[Rust FFI] Error: sample_rate must be in range [8000, 48000] Hz, got {sample_rate}"
+        );
+        return error_result;
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let coeffs = design_biquad(filter_type, freq, q, sample_rate as f32);
+    let filtered = apply_biquad(&coeffs, input_slice);
+
+    detect_pitch_on_slice(&filtered, sample_rate as u32)
+}
+
+/// Detects pitch after conditioning the input through a band-pass filter spanning
+/// `PITCH_MIN_FREQUENCY..PITCH_MAX_FREQUENCY`
+///
+/// Convenience wrapper around `detect_pitch_filtered_rust` so voice-focused callers
+/// get better out-of-the-box behavior without hand-picking a center frequency and Q.
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+#[no_mangle]
+pub unsafe extern "C" fn detect_pitch_voice_band_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+) -> PitchResult {
+    let center = (PITCH_MIN_FREQUENCY * PITCH_MAX_FREQUENCY).sqrt();
+    let bandwidth = PITCH_MAX_FREQUENCY - PITCH_MIN_FREQUENCY;
+    let q = center / bandwidth;
+
+    detect_pitch_filtered_rust(buffer, length, sample_rate, FILTER_TYPE_BANDPASS, center, q)
+}
+
+/// Android JNI native method for detectPitch
+///
+/// JNI Method Signature Resolution:
+/// - Kotlin declaration: `external fun nativeDetectPitch(buffer: FloatArray, sampleRate: Int): PitchResult`
+/// - Package: com.loqalabs.loqaaudiodsp.RustJNI
+/// - Class: RustBridge (object)
+/// - Method: nativeDetectPitch
+/// - JNI Function Name: Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativeDetectPitch
+///
+/// # Arguments
+/// * `env` - JNI environment pointer (unused but required by JNI)
+/// * `class` - JNI class reference (unused but required by JNI)
+/// * `buffer` - JNI jfloatArray reference to input audio samples
+/// * `buffer_length` - Number of samples in buffer
+/// * `sample_rate` - Sample rate in Hz (8000-48000)
+///
+/// # Returns
+/// * PitchResult struct with frequency, confidence, is_voiced, midi_note, and cents
+///
+/// # Safety
+/// * JNI framework ensures proper type conversions and memory management
+/// * This function is called from Kotlin via JNI, not directly
+///
+/// # Note
+/// Unlike FFT, PitchResult is returned by value (small struct), not by pointer.
+/// JNI will automatically marshal this back to Kotlin data class.
+#[no_mangle]
+pub unsafe extern "C" fn Java_com_loqalabs_loqaaudiodsp_RustJNI_RustBridge_nativeDetectPitch(
+    _env: *mut std::os::raw::c_void,
+    _class: *mut std::os::raw::c_void,
+    buffer: *const c_float,
+    buffer_length: c_int,
+    sample_rate: c_int,
+) -> PitchResult {
+    // Delegate to the main pitch detection implementation
+    // The JNI framework handles conversion of FloatArray to *const f32
+    detect_pitch_rust(buffer, buffer_length, sample_rate)
+}
+
+/// Selects the YIN detector (`loqa-voice-dsp`, used by `detect_pitch_rust`)
+pub const PITCH_ALGORITHM_YIN: c_int = 0;
+
+/// Selects the Bitstream Autocorrelation (BACF) detector (`detect_pitch_bacf_rust`)
+pub const PITCH_ALGORITHM_BACF: c_int = 1;
+
+/// Dead-zone around zero used when turning a windowed signal into a 1-bit-per-sample
+/// stream, so samples hovering near zero don't flip the bit back and forth on noise.
+const BACF_HYSTERESIS: f32 = 0.01;
+
+/// How far below the mean distance the curve's minimum must dip to be called voiced
+const BACF_VOICED_THRESHOLD: f32 = 0.7;
+
+/// Packs `samples` into a 1-bit-per-sample stream (LSB-first within each `u64` word)
+/// using a hysteresis zero-crossing extractor: the bit only flips once the signal
+/// clears `BACF_HYSTERESIS` on either side of zero, which suppresses noise flips right
+/// at the crossing.
+fn extract_bacf_bitstream(samples: &[f32]) -> Vec<u64> {
+    let mut words = vec![0u64; samples.len().div_ceil(64)];
+    let mut bit = false;
+
+    for (i, &sample) in samples.iter().enumerate() {
+        if sample > BACF_HYSTERESIS {
+            bit = true;
+        } else if sample < -BACF_HYSTERESIS {
+            bit = false;
+        }
+
+        if bit {
+            words[i / 64] |= 1u64 << (i % 64);
+        }
+    }
+
+    words
+}
+
+/// Reads a 64-bit, bit-aligned window of `words` starting at `bit_index` (funnel shift);
+/// bits past the end of the stream read as 0.
+fn bacf_window_at(words: &[u64], bit_index: usize) -> u64 {
+    let word_index = bit_index / 64;
+    let bit_offset = bit_index % 64;
+
+    if word_index >= words.len() {
+        return 0;
+    }
+
+    let low = words[word_index] >> bit_offset;
+    let high = if bit_offset == 0 || word_index + 1 >= words.len() {
+        0
+    } else {
+        words[word_index + 1] << (64 - bit_offset)
+    };
+
+    low | high
+}
+
+/// Computes the Hamming distance between a bitstream and itself shifted by `lag` bits,
+/// over the `total_bits - lag` bits that overlap, by XOR-ing 64-bit windows and summing
+/// `count_ones()`.
+fn bacf_hamming_distance(words: &[u64], total_bits: usize, lag: usize) -> u32 {
+    let compare_bits = total_bits.saturating_sub(lag);
+    let mut distance = 0u32;
+    let mut bit_index = 0;
+
+    while bit_index < compare_bits {
+        let width = (compare_bits - bit_index).min(64);
+        let a = bacf_window_at(words, bit_index);
+        let b = bacf_window_at(words, bit_index + lag);
+        let mask = if width == 64 { u64::MAX } else { (1u64 << width) - 1 };
+        distance += ((a ^ b) & mask).count_ones();
+        bit_index += 64;
+    }
+
+    distance
+}
+
+/// Walks `distances` (indexed by lag, starting at the shortest lag) and returns the
+/// index of the first local minimum that dips at or below `mean_distance *
+/// BACF_VOICED_THRESHOLD`.
+///
+/// Deliberately NOT the global minimum: an integer multiple of the true period (e.g.
+/// an octave down) tends to correlate even better than the fundamental itself, so
+/// taking `min_by_key` over the whole curve reliably locks onto the wrong period. The
+/// fundamental period is the *first* lag where the bitstream lines back up with
+/// itself closely enough to call it periodic.
+fn find_first_deep_minimum(distances: &[u32], mean_distance: f32) -> Option<usize> {
+    if mean_distance <= 0.0 {
+        return None;
+    }
+
+    let threshold = mean_distance * BACF_VOICED_THRESHOLD;
+
+    for i in 0..distances.len() {
+        let is_local_min = (i == 0 || distances[i] <= distances[i - 1])
+            && (i == distances.len() - 1 || distances[i] <= distances[i + 1]);
+        if is_local_min && (distances[i] as f32) <= threshold {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+/// Detects pitch using Bitstream Autocorrelation (BACF), a low-CPU alternative to YIN
+///
+/// Intended for constrained hardware that can trade a little accuracy for far lower
+/// CPU cost than `detect_pitch_rust`'s YIN algorithm. The input is reduced to a
+/// 1-bit-per-sample stream via a hysteresis zero-crossing extractor, packed into `u64`
+/// words; for each candidate lag the Hamming distance between the stream and a copy
+/// shifted by that lag is computed by XOR-ing words and summing `count_ones()`. The
+/// first deep minimum of the resulting distance-vs-lag curve marks the fundamental
+/// period, refined with parabolic interpolation.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
+///
+/// # Returns
+/// * PitchResult struct with frequency, confidence, is_voiced, midi_note, and cents
+/// * Returns frequency=0.0, confidence=0.0, is_voiced=false on error
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * This function dereferences raw pointers and is inherently unsafe
+/// * Buffer must remain valid for the duration of this function call
+///
+/// # Validation
+/// * Sample rate must be between 8000 and 48000 Hz, matching `detect_pitch_rust`
+/// * The analysis window must span at least two periods of `PITCH_MIN_FREQUENCY`,
+///   otherwise there isn't enough signal to find the fundamental and the result is unvoiced
+#[no_mangle]
+pub unsafe extern "C" fn detect_pitch_bacf_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+) -> PitchResult {
+    let error_result = PitchResult {
+        frequency: 0.0,
+        confidence: 0.0,
+        is_voiced: false,
+        midi_note: 0,
+        cents: 0,
+    };
+
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return error_result;
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return error_result;
+    }
+
+    if !(8000..=48000).contains(&sample_rate) {
+        eprintln!(
+            "[Rust FFI] Error: sample_rate must be in range [8000, 48000] Hz, got {sample_rate}"
+        );
+        return error_result;
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let sample_rate_f = sample_rate as f32;
+
+    let min_period = (sample_rate_f / PITCH_MAX_FREQUENCY).round() as usize;
+    let max_period = (sample_rate_f / PITCH_MIN_FREQUENCY).round() as usize;
+
+    if input_slice.len() < 2 * max_period {
+        eprintln!(
+            "[Rust FFI] Error: buffer of {} samples is too short for BACF (need at least {} samples)",
+            input_slice.len(),
+            2 * max_period
+        );
+        return error_result;
+    }
+
+    let words = extract_bacf_bitstream(input_slice);
+    let total_bits = input_slice.len();
+
+    let distances: Vec<u32> = (min_period..=max_period)
+        .map(|lag| bacf_hamming_distance(&words, total_bits, lag))
+        .collect();
+
+    let mean_distance = distances.iter().copied().sum::<u32>() as f32 / distances.len() as f32;
+
+    // The first deep minimum (not the global minimum) marks the fundamental period;
+    // see `find_first_deep_minimum` for why.
+    let min_offset = match find_first_deep_minimum(&distances, mean_distance) {
+        Some(offset) => offset,
+        None => return error_result,
+    };
+    let min_distance = distances[min_offset];
+
+    // Parabolic interpolation around the minimum for a sub-sample period estimate.
+    let refined_offset = if min_offset > 0 && min_offset + 1 < distances.len() {
+        let y_minus = distances[min_offset - 1] as f32;
+        let y_center = distances[min_offset] as f32;
+        let y_plus = distances[min_offset + 1] as f32;
+        let denom = y_minus - 2.0 * y_center + y_plus;
+        if denom.abs() > f32::EPSILON {
+            min_offset as f32 + 0.5 * (y_minus - y_plus) / denom
+        } else {
+            min_offset as f32
+        }
+    } else {
+        min_offset as f32
+    };
+
+    let period = min_period as f32 + refined_offset;
+    if period <= 0.0 {
+        return error_result;
+    }
+
+    let frequency = sample_rate_f / period;
+    let confidence = (1.0 - (min_distance as f32 / total_bits as f32)).clamp(0.0, 1.0);
+    let (midi_note, cents) = nearest_midi_and_cents(frequency);
+
+    PitchResult {
+        frequency,
+        confidence,
+        is_voiced: true,
+        midi_note,
+        cents,
+    }
+}
+
+/// Detects pitch using a caller-selected algorithm
+///
+/// Dispatches to `detect_pitch_rust` (YIN, more accurate) or `detect_pitch_bacf_rust`
+/// (BACF, far cheaper on CPU) based on `algorithm`, so callers on constrained hardware
+/// can make that accuracy/cost tradeoff with a single argument instead of branching
+/// between two function names themselves.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
+/// * `algorithm` - `PITCH_ALGORITHM_YIN` (0) or `PITCH_ALGORITHM_BACF` (1); unknown
+///   values fall back to YIN
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * This function dereferences raw pointers and is inherently unsafe
+#[no_mangle]
+pub unsafe extern "C" fn detect_pitch_with_algorithm_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    algorithm: c_int,
+) -> PitchResult {
+    if algorithm == PITCH_ALGORITHM_BACF {
+        detect_pitch_bacf_rust(buffer, length, sample_rate)
+    } else {
+        detect_pitch_rust(buffer, length, sample_rate)
+    }
+}
+
+/// Maximum length (including the null terminator) of a `NoteResult::note_name` buffer
+const NOTE_NAME_BUFFER_LEN: usize = 4;
+
+/// Equal-tempered pitch-class names, indexed by `midi_note mod 12` (0 = C)
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Converts a MIDI note number to frequency in Hz (A4 = MIDI 69 = 440 Hz)
+fn midi_to_frequency(midi_note: i32) -> f32 {
+    440.0 * 2f32.powf((midi_note - 69) as f32 / 12.0)
+}
+
+/// Resolves a frequency in Hz to the nearest MIDI note number and signed cents
+/// deviation from equal temperament, roughly in `[-50, 50]`.
+fn nearest_midi_and_cents(frequency: f32) -> (i32, i32) {
+    let midi_note = (69.0 + 12.0 * (frequency / 440.0).log2()).round() as i32;
+    let f_nearest = midi_to_frequency(midi_note);
+    let cents = (1200.0 * (frequency / f_nearest).log2()).round() as i32;
+    (midi_note, cents.clamp(-50, 50))
+}
+
+/// Result structure for note detection: pitch plus the nearest equal-tempered note
+///
+/// This struct is C-compatible for FFI/JNI interop.
+///
+/// # Fields
+/// * `frequency` - Detected pitch in Hz (0.0 if unvoiced or no pitch detected)
+/// * `confidence` - Confidence score from 0.0 (low) to 1.0 (high)
+/// * `is_voiced` - Whether the audio segment is voiced (true) or unvoiced (false)
+/// * `note_name` - Nearest note name as a null-terminated ASCII string (e.g. "A#4\0"),
+///   zeroed when `is_voiced` is false
+/// * `cents` - Signed tuning error in cents, roughly in `[-50, 50]`, zeroed when
+///   `is_voiced` is false
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NoteResult {
+    pub frequency: c_float,
+    pub confidence: c_float,
+    pub is_voiced: bool,
+    pub note_name: [c_char; NOTE_NAME_BUFFER_LEN],
+    pub cents: c_int,
+}
+
+/// Detects pitch and resolves it to the nearest musical note and tuning error
+///
+/// Builds on `detect_pitch_rust` so tuner-style apps don't have to reimplement the
+/// frequency-to-note conversion in Swift/Kotlin.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
+///
+/// # Returns
+/// * `NoteResult` with frequency/confidence/is_voiced from `detect_pitch_rust`, plus
+///   `note_name` and `cents`. `note_name` and `cents` are zeroed when `is_voiced` is false.
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * This function dereferences raw pointers and is inherently unsafe
+/// * Buffer must remain valid for the duration of this function call
+///
+/// # Note Conversion
+/// MIDI note number is `round(69 + 12*log2(frequency/440))`; the note name is looked
+/// up by `midi mod 12` and the octave is `midi/12 - 1`. Cents off is
+/// `1200*log2(frequency/f_nearest)` where `f_nearest = 440*2^((midi-69)/12)`.
+#[no_mangle]
+pub unsafe extern "C" fn detect_note_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+) -> NoteResult {
+    let pitch = detect_pitch_rust(buffer, length, sample_rate);
+
+    let mut result = NoteResult {
+        frequency: pitch.frequency,
+        confidence: pitch.confidence,
+        is_voiced: pitch.is_voiced,
+        note_name: [0; NOTE_NAME_BUFFER_LEN],
+        cents: 0,
+    };
+
+    if !pitch.is_voiced || pitch.frequency <= 0.0 {
+        return result;
+    }
+
+    let (midi_note, cents) = nearest_midi_and_cents(pitch.frequency);
+    result.cents = cents;
+
+    let pitch_class = midi_note.rem_euclid(12) as usize;
+    let octave = midi_note.div_euclid(12) - 1;
+    let label = format!("{}{}", NOTE_NAMES[pitch_class], octave);
+
+    // note_name is a fixed 4-byte buffer (3 visible chars + null terminator);
+    // truncate rather than overflow for notes whose label doesn't fit.
+    let label_bytes = label.as_bytes();
+    let copy_len = label_bytes.len().min(NOTE_NAME_BUFFER_LEN - 1);
+    for (dst, &src) in result.note_name[..copy_len].iter_mut().zip(label_bytes) {
+        *dst = src as c_char;
+    }
+
+    result
+}
+
+/// Upper bound on how many computed-but-unpolled frames an `Analyzer` will hold.
+///
+/// A single `analyzer_push_rust` call can supply enough samples to compute several
+/// frames (e.g. a caller forwarding whatever-sized buffer their capture callback
+/// hands them, rather than hand-chunking to exactly `hop_size`). Every frame is kept
+/// up to this bound so none of that FFT work is wasted; beyond it, the oldest pending
+/// frame is dropped (and logged) so the queue can't grow without limit if the caller
+/// never polls.
+const ANALYZER_MAX_PENDING_FRAMES: usize = 16;
+
+/// Opaque handle for continuous, overlapping-frame FFT analysis
+///
+/// `Analyzer` owns a ring buffer of incoming samples and emits overlapping frames
+/// (stepped by `hop_size`) without callers having to manage frame alignment themselves.
+/// Each frame is copied into a newly allocated buffer and transformed with
+/// `loqa_voice_dsp::compute_fft`, the same call `compute_fft_rust` makes; no FFT plan or
+/// scratch buffer is kept between frames.
+pub struct Analyzer {
+    sample_rate: u32,
+    fft_size: usize,
+    hop_size: usize,
+    samples: VecDeque<f32>,
+    pending_frames: VecDeque<Vec<f32>>,
+}
+
+/// Creates an `Analyzer` handle for streaming FFT analysis
+///
+/// # Arguments
+/// * `sample_rate` - Sample rate in Hz (must be > 0)
+/// * `fft_size` - FFT size (must be power of 2, range: 256-8192)
+/// * `hop_size` - Number of samples the analysis window slides forward per frame
+///   (must be > 0 and <= `fft_size`)
+///
+/// # Returns
+/// * Pointer to a new `Analyzer`, or null on invalid arguments
+///
+/// # Safety
+/// * Caller MUST call `analyzer_destroy_rust` to free the returned handle
+#[no_mangle]
+pub unsafe extern "C" fn analyzer_create_rust(
+    sample_rate: c_int,
+    fft_size: c_int,
+    hop_size: c_int,
+) -> *mut Analyzer {
+    if sample_rate <= 0 {
+        eprintln!("[Rust FFI] Error: sample_rate must be > 0, got {sample_rate}");
+        return std::ptr::null_mut();
+    }
+
+    let fft_size_usize = fft_size as usize;
+    if fft_size <= 0 || (fft_size_usize & (fft_size_usize - 1)) != 0 {
+        eprintln!("[Rust FFI] Error: fft_size must be power of 2, got {fft_size}");
+        return std::ptr::null_mut();
+    }
+
+    if !(256..=8192).contains(&fft_size) {
+        eprintln!("[Rust FFI] Error: fft_size must be in range [256, 8192], got {fft_size}");
+        return std::ptr::null_mut();
+    }
+
+    if hop_size <= 0 || hop_size as usize > fft_size_usize {
+        eprintln!(
+            "[Rust FFI] Error: hop_size must be in range (0, fft_size], got {hop_size}"
+        );
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(Analyzer {
+        sample_rate: sample_rate as u32,
+        fft_size: fft_size_usize,
+        hop_size: hop_size as usize,
+        samples: VecDeque::with_capacity(fft_size_usize * 2),
+        pending_frames: VecDeque::new(),
+    }))
+}
+
+/// Pushes newly captured samples into an `Analyzer`
+///
+/// Appends `buffer` to the analyzer's internal ring buffer. Whenever at least
+/// `fft_size` samples are buffered, computes the FFT of the oldest full frame,
+/// queues it for `analyzer_poll_fft_rust`, and slides the window forward by
+/// `hop_size` samples, repeating until fewer than `fft_size` samples remain. If a
+/// single call produces enough frames to exceed `ANALYZER_MAX_PENDING_FRAMES`, the
+/// oldest queued frame is dropped (and logged) to bound memory use rather than
+/// silently discarding every frame but the last.
+///
+/// # Arguments
+/// * `handle` - Handle returned by `analyzer_create_rust`
+/// * `buffer` - Pointer to newly captured audio samples (Float32 array)
+/// * `length` - Number of samples in `buffer`
+///
+/// # Returns
+/// * `0` on success, `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * `handle` must be a valid, non-destroyed pointer from `analyzer_create_rust`
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+#[no_mangle]
+pub unsafe extern "C" fn analyzer_push_rust(
+    handle: *mut Analyzer,
+    buffer: *const c_float,
+    length: c_int,
+) -> c_int {
+    if handle.is_null() {
+        eprintln!("[Rust FFI] Error: handle pointer is null");
+        return -1;
+    }
+
+    if buffer.is_null() || length <= 0 {
+        eprintln!("[Rust FFI] Error: buffer pointer is null or length <= 0");
+        return -1;
+    }
+
+    let analyzer = &mut *handle;
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    analyzer.samples.extend(input_slice.iter().copied());
+
+    while analyzer.samples.len() >= analyzer.fft_size {
+        let frame: Vec<f32> = analyzer.samples.iter().take(analyzer.fft_size).copied().collect();
+
+        match loqa_voice_dsp::compute_fft(&frame, analyzer.sample_rate, analyzer.fft_size) {
+            Ok(result) => {
+                if analyzer.pending_frames.len() >= ANALYZER_MAX_PENDING_FRAMES {
+                    eprintln!(
+                        "[Rust FFI] Warning: Analyzer pending frame queue full (>= {ANALYZER_MAX_PENDING_FRAMES}), dropping oldest frame"
+                    );
+                    analyzer.pending_frames.pop_front();
+                }
+                analyzer.pending_frames.push_back(result.magnitudes);
+            }
+            Err(e) => {
+                eprintln!("[Rust FFI] Analyzer FFT computation failed: {e:?}");
+                return -1;
+            }
+        }
+
+        for _ in 0..analyzer.hop_size {
+            analyzer.samples.pop_front();
+        }
+    }
+
+    0
+}
+
+/// Drains the oldest not-yet-polled frame from an `Analyzer`
+///
+/// Frames are queued in the order they were computed (FIFO), up to
+/// `ANALYZER_MAX_PENDING_FRAMES`; call this repeatedly (until it returns `1`) to drain
+/// every frame a `analyzer_push_rust` call produced rather than just the latest one.
+///
+/// # Arguments
+/// * `handle` - Handle returned by `analyzer_create_rust`
+/// * `out_magnitudes` - Caller-allocated buffer of at least `fft_size/2 + 1` floats
+///   to receive the magnitude spectrum
+///
+/// # Returns
+/// * `0` if a frame was written to `out_magnitudes`
+/// * `1` if no queued frame is ready yet (not enough samples pushed since the last poll)
+/// * `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * `handle` must be a valid, non-destroyed pointer from `analyzer_create_rust`
+/// * `out_magnitudes` must point to valid, writable memory of at least `fft_size/2 + 1` floats
+#[no_mangle]
+pub unsafe extern "C" fn analyzer_poll_fft_rust(
+    handle: *mut Analyzer,
+    out_magnitudes: *mut c_float,
+) -> c_int {
+    if handle.is_null() {
+        eprintln!("[Rust FFI] Error: handle pointer is null");
+        return -1;
+    }
+
+    if out_magnitudes.is_null() {
+        eprintln!("[Rust FFI] Error: out_magnitudes pointer is null");
+        return -1;
+    }
+
+    let analyzer = &mut *handle;
+    let Some(magnitudes) = analyzer.pending_frames.pop_front() else {
+        return 1;
+    };
+
+    let out_slice = slice::from_raw_parts_mut(out_magnitudes, magnitudes.len());
+    out_slice.copy_from_slice(&magnitudes);
+
+    0
+}
+
+/// Destroys an `Analyzer` handle created by `analyzer_create_rust`
+///
+/// # Safety
+/// * Must only be called once per handle
+/// * `handle` must not be used after this call
+/// * Null pointers are handled gracefully and do nothing
+#[no_mangle]
+pub unsafe extern "C" fn analyzer_destroy_rust(handle: *mut Analyzer) {
+    if handle.is_null() {
+        return;
+    }
+
+    let _ = Box::from_raw(handle);
+}
+
+/// Converts a time constant in milliseconds to a per-sample exponential smoothing
+/// coefficient for a given sample rate: `exp(-1 / (time_ms * 0.001 * sample_rate))`.
+fn time_ms_to_coefficient(time_ms: f32, sample_rate: f32) -> f32 {
+    (-1.0 / (time_ms * 0.001 * sample_rate)).exp()
+}
+
+/// Smallest magnitude treated as non-zero when converting to dB, to avoid `log10(0)`
+const ENVELOPE_EPSILON: f32 = 1e-9;
+
+/// Computes a smoothed peak envelope of an audio buffer
+///
+/// Runs a per-sample attack/release envelope follower: `env` eases toward `|x|` using
+/// the attack coefficient while rising and the release coefficient while falling, so
+/// downstream stages (e.g. `apply_compression_rust`) see a smooth level rather than
+/// the raw, jittery sample magnitude.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be > 0)
+/// * `attack_ms` - Attack time constant in milliseconds (must be > 0)
+/// * `release_ms` - Release time constant in milliseconds (must be > 0)
+/// * `out_env` - Caller-allocated buffer of at least `length` floats to receive the envelope
+///
+/// # Returns
+/// * `0` on success, `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * Caller must ensure `out_env` points to valid, writable memory of at least `length` floats
+#[no_mangle]
+pub unsafe extern "C" fn compute_envelope_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    attack_ms: c_float,
+    release_ms: c_float,
+    out_env: *mut c_float,
+) -> c_int {
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return -1;
+    }
+
+    if out_env.is_null() {
+        eprintln!("[Rust FFI] Error: out_env pointer is null");
+        return -1;
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return -1;
+    }
+
+    if sample_rate <= 0 {
+        eprintln!("[Rust FFI] Error: sample_rate must be > 0, got {sample_rate}");
+        return -1;
+    }
+
+    if attack_ms <= 0.0 || release_ms <= 0.0 {
+        eprintln!(
+            "[Rust FFI] Error: attack_ms and release_ms must be > 0, got {attack_ms}, {release_ms}"
+        );
+        return -1;
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let out_slice = slice::from_raw_parts_mut(out_env, length as usize);
+
+    let attack_coef = time_ms_to_coefficient(attack_ms, sample_rate as f32);
+    let release_coef = time_ms_to_coefficient(release_ms, sample_rate as f32);
+
+    let mut env = 0.0_f32;
+    for (sample, out) in input_slice.iter().zip(out_slice.iter_mut()) {
+        let x = sample.abs();
+        env = if x > env {
+            x + attack_coef * (env - x)
+        } else {
+            x + release_coef * (env - x)
+        };
+        *out = env;
+    }
+
+    0
+}
+
+/// Applies a WDRC-style dynamic range compressor to an audio buffer
+///
+/// Runs the same attack/release envelope follower as `compute_envelope_rust`, then
+/// computes gain in the log domain: below `threshold_db` gain is unity; above it,
+/// `gain_db = (1 - 1/ratio) * (threshold_db - level_db)`. This lets quiet/loud mic
+/// input get normalized toward a consistent level before pitch/FFT analysis, so faint
+/// speech still yields a voiced pitch detection.
+///
+/// # Arguments
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be > 0)
+/// * `threshold_db` - Level above which compression engages, in dB relative to full scale
+/// * `ratio` - Compression ratio (must be >= 1.0; 1.0 = no compression)
+/// * `attack_ms` - Attack time constant in milliseconds (must be > 0)
+/// * `release_ms` - Release time constant in milliseconds (must be > 0)
+/// * `makeup_db` - Makeup gain applied after compression, in dB
+/// * `out_buffer` - Caller-allocated buffer of at least `length` floats to receive the
+///   compressed samples
+///
+/// # Returns
+/// * `0` on success, `-1` on error (see stderr for details)
+///
+/// # Safety
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+/// * Caller must ensure `out_buffer` points to valid, writable memory of at least
+///   `length` floats
+#[no_mangle]
+pub unsafe extern "C" fn apply_compression_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    threshold_db: c_float,
+    ratio: c_float,
+    attack_ms: c_float,
+    release_ms: c_float,
+    makeup_db: c_float,
+    out_buffer: *mut c_float,
+) -> c_int {
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return -1;
+    }
+
+    if out_buffer.is_null() {
+        eprintln!("[Rust FFI] Error: out_buffer pointer is null");
+        return -1;
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return -1;
+    }
+
+    if sample_rate <= 0 {
+        eprintln!("[Rust FFI] Error: sample_rate must be > 0, got {sample_rate}");
+        return -1;
+    }
+
+    if ratio < 1.0 {
+        eprintln!("[Rust FFI] Error: ratio must be >= 1.0, got {ratio}");
+        return -1;
+    }
+
+    if attack_ms <= 0.0 || release_ms <= 0.0 {
+        eprintln!(
+            "[Rust FFI] Error: attack_ms and release_ms must be > 0, got {attack_ms}, {release_ms}"
+        );
+        return -1;
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let out_slice = slice::from_raw_parts_mut(out_buffer, length as usize);
+
+    let attack_coef = time_ms_to_coefficient(attack_ms, sample_rate as f32);
+    let release_coef = time_ms_to_coefficient(release_ms, sample_rate as f32);
+    let makeup_linear = 10f32.powf(makeup_db / 20.0);
+
+    let mut env = 0.0_f32;
+    for (sample, out) in input_slice.iter().zip(out_slice.iter_mut()) {
+        let x = sample.abs();
+        env = if x > env {
+            x + attack_coef * (env - x)
+        } else {
+            x + release_coef * (env - x)
+        };
+
+        let level_db = 20.0 * env.max(ENVELOPE_EPSILON).log10();
+        let gain_db = if level_db > threshold_db {
+            (1.0 - 1.0 / ratio) * (threshold_db - level_db)
+        } else {
+            0.0
+        };
+
+        let gain_linear = 10f32.powf(gain_db / 20.0) * makeup_linear;
+        *out = sample * gain_linear;
+    }
+
+    0
+}
+
+/// Relative tolerance used to detect an octave jump: a raw estimate within this
+/// fraction of half or double the tracker's running median is snapped to the
+/// median's octave instead of accepted at face value.
+const PITCH_TRACKER_OCTAVE_TOLERANCE: f32 = 0.07;
+
+/// Returns the median of `values` (assumed non-empty)
+fn median(values: &[f32]) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Snaps `raw_freq` to the correct octave if it looks like an octave-jump error
+/// relative to `running_median` (i.e. it's close to half or double it).
+fn correct_octave(raw_freq: f32, running_median: f32) -> f32 {
+    if running_median <= 0.0 {
+        return raw_freq;
+    }
+
+    let half = running_median * 0.5;
+    let double = running_median * 2.0;
+
+    if (raw_freq - half).abs() / half < PITCH_TRACKER_OCTAVE_TOLERANCE {
+        raw_freq * 2.0
+    } else if (raw_freq - double).abs() / double < PITCH_TRACKER_OCTAVE_TOLERANCE {
+        raw_freq * 0.5
+    } else {
+        raw_freq
+    }
+}
+
+/// Opaque stateful multi-frame pitch tracker
+///
+/// Feeds successive frames through `detect_pitch_rust` and returns a median-filtered
+/// frequency instead of the instantaneous estimate, so octave jumps and isolated
+/// spurious estimates (e.g. the noise behavior exercised by
+/// `test_detect_pitch_noise_behavior`) get smoothed out into a stable, jitter-free
+/// pitch contour.
+pub struct PitchTracker {
+    recent_frequencies: VecDeque<f32>,
+    window_len: usize,
+    decimation_rate: usize,
+    frame_counter: usize,
+    last_result: PitchResult,
+}
+
+fn pitch_tracker_unvoiced_result() -> PitchResult {
+    PitchResult {
+        frequency: 0.0,
+        confidence: 0.0,
+        is_voiced: false,
+        midi_note: 0,
+        cents: 0,
+    }
+}
+
+/// Creates a `PitchTracker` handle
+///
+/// # Arguments
+/// * `window_len` - Number of recent voiced estimates to median-filter over (must be > 0)
+/// * `decimation_rate` - Run full detection every `decimation_rate`-th call to
+///   `pitch_tracker_push_rust`, reusing the last result otherwise (must be > 0; 1 runs
+///   detection on every frame)
+///
+/// # Returns
+/// * Pointer to a new `PitchTracker`, or null on invalid arguments
+///
+/// # Safety
+/// * Caller MUST call `pitch_tracker_destroy_rust` to free the returned handle
+#[no_mangle]
+pub unsafe extern "C" fn pitch_tracker_create_rust(
+    window_len: c_int,
+    decimation_rate: c_int,
+) -> *mut PitchTracker {
+    if window_len <= 0 {
+        eprintln!("[Rust FFI] Error: window_len must be > 0, got {window_len}");
+        return std::ptr::null_mut();
+    }
+
+    if decimation_rate <= 0 {
+        eprintln!("[Rust FFI] Error: decimation_rate must be > 0, got {decimation_rate}");
+        return std::ptr::null_mut();
+    }
+
+    Box::into_raw(Box::new(PitchTracker {
+        recent_frequencies: VecDeque::with_capacity(window_len as usize),
+        window_len: window_len as usize,
+        decimation_rate: decimation_rate as usize,
+        frame_counter: 0,
+        last_result: pitch_tracker_unvoiced_result(),
+    }))
+}
+
+/// Pushes one frame of samples through the tracker and returns the tracked pitch
+///
+/// Every `decimation_rate`-th call runs `detect_pitch_rust`, corrects octave jumps
+/// against the tracker's running median, appends the corrected frequency to the
+/// window, and returns the new median as `frequency` (with `midi_note`/`cents`
+/// recomputed from it). Calls in between reuse the last returned result unchanged.
+/// Unvoiced frames don't update the window, so a later voiced frame still compares
+/// against the last good median rather than resetting it.
+///
+/// # Arguments
+/// * `handle` - Handle returned by `pitch_tracker_create_rust`
+/// * `buffer` - Pointer to input audio samples (Float32 array)
+/// * `length` - Number of samples in input buffer
+/// * `sample_rate` - Sample rate in Hz (must be 8000-48000 Hz)
+///
+/// # Returns
+/// * Tracked `PitchResult`; `is_voiced=false` if the most recent detected (or reused) frame was unvoiced
+///
+/// # Safety
+/// * `handle` must be a valid, non-destroyed pointer from `pitch_tracker_create_rust`
+/// * Caller must ensure `buffer` points to valid memory of at least `length` samples
+#[no_mangle]
+pub unsafe extern "C" fn pitch_tracker_push_rust(
+    handle: *mut PitchTracker,
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+) -> PitchResult {
+    if handle.is_null() {
+        eprintln!("[Rust FFI] Error: handle pointer is null");
+        return pitch_tracker_unvoiced_result();
+    }
+
+    let tracker = &mut *handle;
+    let should_run = tracker.frame_counter % tracker.decimation_rate == 0;
+    tracker.frame_counter += 1;
+
+    if !should_run {
+        return tracker.last_result;
+    }
+
+    let raw = detect_pitch_rust(buffer, length, sample_rate);
+
+    if !raw.is_voiced {
+        tracker.last_result = pitch_tracker_unvoiced_result();
+        return tracker.last_result;
+    }
+
+    let running_median = if tracker.recent_frequencies.is_empty() {
+        raw.frequency
+    } else {
+        median(tracker.recent_frequencies.make_contiguous())
+    };
+
+    let corrected_frequency = correct_octave(raw.frequency, running_median);
+
+    if tracker.recent_frequencies.len() >= tracker.window_len {
+        tracker.recent_frequencies.pop_front();
+    }
+    tracker.recent_frequencies.push_back(corrected_frequency);
+
+    let tracked_frequency = median(tracker.recent_frequencies.make_contiguous());
+    let (midi_note, cents) = nearest_midi_and_cents(tracked_frequency);
+
+    tracker.last_result = PitchResult {
+        frequency: tracked_frequency,
+        confidence: raw.confidence,
+        is_voiced: true,
+        midi_note,
+        cents,
+    };
+
+    tracker.last_result
+}
+
+/// Resets a `PitchTracker`'s window and decimation counter to a fresh state
+///
+/// # Safety
+/// * `handle` must be a valid, non-destroyed pointer from `pitch_tracker_create_rust`
+#[no_mangle]
+pub unsafe extern "C" fn pitch_tracker_reset_rust(handle: *mut PitchTracker) {
+    if handle.is_null() {
+        return;
+    }
+
+    let tracker = &mut *handle;
+    tracker.recent_frequencies.clear();
+    tracker.frame_counter = 0;
+    tracker.last_result = pitch_tracker_unvoiced_result();
+}
+
+/// Destroys a `PitchTracker` handle created by `pitch_tracker_create_rust`
+///
+/// # Safety
+/// * Must only be called once per handle
+/// * `handle` must not be used after this call
+/// * Null pointers are handled gracefully and do nothing
+#[no_mangle]
+pub unsafe extern "C" fn pitch_tracker_destroy_rust(handle: *mut PitchTracker) {
+    if handle.is_null() {
+        return;
+    }
+
+    let _ = Box::from_raw(handle);
+}
+
+/// Placeholder FFI function for testing build infrastructure (retained for backward compatibility)
+#[no_mangle]
+pub extern "C" fn test_ffi_bridge() -> i32 {
+    42
+}
+
+/// FFT size used internally by the phase vocoder pitch shifter.
+///
+/// Large enough for good frequency resolution on voice-range material while staying
+/// cheap enough for the overlap-add loop to run comfortably in real time.
+const PITCH_SHIFT_FFT_SIZE: usize = 2048;
+
+/// Hop size between analysis frames: 75% overlap, as requested.
+const PITCH_SHIFT_HOP_SIZE: usize = PITCH_SHIFT_FFT_SIZE / 4;
+
+/// Minimal complex number used by the phase vocoder's internal FFT.
+///
+/// Kept local rather than pulling in an external numeric crate, since it's used by
+/// exactly one subsystem.
+#[derive(Debug, Clone, Copy)]
+struct Complex32 {
+    re: f32,
+    im: f32,
+}
+
+impl Complex32 {
+    fn new(re: f32, im: f32) -> Self {
+        Complex32 { re, im }
+    }
+
+    fn zero() -> Self {
+        Complex32::new(0.0, 0.0)
+    }
+
+    fn add(self, other: Complex32) -> Complex32 {
+        Complex32::new(self.re + other.re, self.im + other.im)
+    }
+
+    fn sub(self, other: Complex32) -> Complex32 {
+        Complex32::new(self.re - other.re, self.im - other.im)
+    }
+
+    fn mul(self, other: Complex32) -> Complex32 {
+        Complex32::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    fn magnitude(self) -> f32 {
+        (self.re * self.re + self.im * self.im).sqrt()
+    }
+
+    fn phase(self) -> f32 {
+        self.im.atan2(self.re)
+    }
+
+    fn from_polar(magnitude: f32, phase: f32) -> Complex32 {
+        Complex32::new(magnitude * phase.cos(), magnitude * phase.sin())
+    }
+}
+
+/// In-place iterative radix-2 Cooley-Tukey FFT.
+///
+/// Runs the forward transform when `inverse` is false, or the inverse transform
+/// (including the `1/n` scaling) when `inverse` is true. `data.len()` must be a
+/// power of two.
+fn fft_in_place(data: &mut [Complex32], inverse: bool) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+
+    // Bit-reversal permutation
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f32;
+        let wlen = Complex32::new(angle.cos(), angle.sin());
+        let mut i = 0;
+        while i < n {
+            let mut w = Complex32::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let u = data[i + k];
+                let v = data[i + k + len / 2].mul(w);
+                data[i + k] = u.add(v);
+                data[i + k + len / 2] = u.sub(v);
+                w = w.mul(wlen);
+            }
+            i += len;
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f32;
+        for c in data.iter_mut() {
+            c.re *= scale;
+            c.im *= scale;
+        }
+    }
+}
+
+/// Shifts the pitch of `samples` by `shift_ratio` using an STFT phase vocoder.
+///
+/// Analysis frames of `PITCH_SHIFT_FFT_SIZE` samples are extracted with 75% overlap
+/// and Hann-windowed. Each bin's true frequency is estimated from the phase difference
+/// between consecutive frames divided by the hop time, then both magnitude and true
+/// frequency are remapped to `round(bin * shift_ratio)`, accumulating magnitude when
+/// two source bins land on the same target bin. Output phase is resynthesized by
+/// integrating the shifted frequencies across hops, and frames are reconstructed via
+/// inverse FFT and overlap-add. Returns a buffer the same length as `samples`; inputs
+/// shorter than one FFT frame are returned unchanged.
+///
+/// The input is zero-padded by `fft_size - hop_size` samples at the start and `fft_size`
+/// samples at the end before framing (standard STFT priming; the extra `hop_size` at the
+/// end covers lengths that are not a multiple of `hop_size`) so every *original* sample
+/// falls inside the region
+/// where overlap-add has reached full, steady-state coverage; the padding is trimmed
+/// back off before returning. Without this, the first/last `fft_size - hop_size`
+/// samples of every call would only be covered by a partial stack of frames and would
+/// have to be discarded or under-normalized.
+fn phase_vocoder_pitch_shift(samples: &[f32], shift_ratio: f32) -> Vec<f32> {
+    let fft_size = PITCH_SHIFT_FFT_SIZE;
+    let hop_size = PITCH_SHIFT_HOP_SIZE;
+    let num_bins = fft_size / 2 + 1;
+
+    if samples.len() < fft_size || shift_ratio <= 0.0 {
+        return samples.to_vec();
+    }
+
+    let pad = fft_size - hop_size;
+    let mut padded = vec![0.0f32; pad];
+    padded.extend_from_slice(samples);
+    padded.resize(padded.len() + pad + hop_size, 0.0);
+
+    let mut window = vec![0.0f32; fft_size];
+    for (n, w) in window.iter_mut().enumerate() {
+        *w = 0.5 - 0.5 * (2.0 * PI * n as f32 / (fft_size - 1) as f32).cos();
+    }
+
+    let mut output = vec![0.0f32; padded.len()];
+    let mut window_sum = vec![0.0f32; padded.len()];
+
+    let mut last_input_phase = vec![0.0f32; num_bins];
+    let mut sum_output_phase = vec![0.0f32; num_bins];
+
+    // Phase advance expected from a bin's nominal frequency alone, over one hop.
+    let expected_phase_advance: Vec<f32> = (0..num_bins)
+        .map(|k| 2.0 * PI * k as f32 * hop_size as f32 / fft_size as f32)
+        .collect();
+
+    let mut frame_start = 0;
+    while frame_start + fft_size <= padded.len() {
+        let mut frame: Vec<Complex32> = padded[frame_start..frame_start + fft_size]
+            .iter()
+            .zip(window.iter())
+            .map(|(sample, w)| Complex32::new(sample * w, 0.0))
+            .collect();
+
+        fft_in_place(&mut frame, false);
+
+        let mut true_freq = vec![0.0f32; num_bins];
+        let mut magnitude = vec![0.0f32; num_bins];
+        for k in 0..num_bins {
+            let bin = frame[k];
+            magnitude[k] = bin.magnitude();
+
+            let phase = bin.phase();
+            let mut phase_diff = phase - last_input_phase[k] - expected_phase_advance[k];
+            last_input_phase[k] = phase;
+
+            // Wrap into [-PI, PI] so the deviation reflects the nearest cycle
+            phase_diff = ((phase_diff + PI).rem_euclid(2.0 * PI)) - PI;
+
+            let deviation_per_sample = phase_diff / hop_size as f32;
+            let bin_freq = 2.0 * PI * k as f32 / fft_size as f32;
+            true_freq[k] = bin_freq + deviation_per_sample;
+        }
+
+        // Remap magnitude and true frequency to shift-ratio-scaled target bins,
+        // accumulating magnitude on collisions.
+        let mut shifted_magnitude = vec![0.0f32; num_bins];
+        let mut shifted_freq = vec![0.0f32; num_bins];
+        for k in 0..num_bins {
+            if magnitude[k] <= 0.0 {
+                continue;
+            }
+            let target_bin = (k as f32 * shift_ratio).round();
+            if target_bin < 0.0 || target_bin as usize >= num_bins {
+                continue;
+            }
+            let target_bin = target_bin as usize;
+            shifted_magnitude[target_bin] += magnitude[k];
+            shifted_freq[target_bin] = true_freq[k] * shift_ratio;
+        }
+
+        let mut synthesis_frame = vec![Complex32::zero(); fft_size];
+        for k in 0..num_bins {
+            if shifted_magnitude[k] <= 0.0 {
+                sum_output_phase[k] = 0.0;
+                continue;
+            }
+
+            // Resynthesize phase by integrating the shifted frequency across this hop
+            sum_output_phase[k] += shifted_freq[k] * hop_size as f32;
+            let bin_value = Complex32::from_polar(shifted_magnitude[k], sum_output_phase[k]);
+            synthesis_frame[k] = bin_value;
+
+            // Real-valued signal: mirror into the conjugate-symmetric upper half
+            if k != 0 && k != fft_size / 2 {
+                synthesis_frame[fft_size - k] = Complex32::new(bin_value.re, -bin_value.im);
+            }
+        }
+
+        fft_in_place(&mut synthesis_frame, true);
+
+        for n in 0..fft_size {
+            output[frame_start + n] += synthesis_frame[n].re * window[n];
+            window_sum[frame_start + n] += window[n] * window[n];
+        }
+
+        frame_start += hop_size;
+    }
+
+    // Within the padding region itself (which gets trimmed off below), overlap hasn't
+    // built up to steady state, and for `shift_ratio != 1.0` the resynthesized tone has
+    // a roughly constant envelope across the frame (it no longer carries the analysis
+    // window's taper the way the `shift_ratio == 1.0` case does), so dividing by a
+    // near-zero `window_sum` there would blow up. The padding guarantees every *kept*
+    // sample already has full overlap, so this is purely a safety net against dividing
+    // by (near) zero in the discarded edges.
+    let steady_state = window_sum.iter().cloned().fold(0.0f32, f32::max);
+    let normalization_floor = steady_state * 0.9;
+    for (sample, norm) in output.iter_mut().zip(window_sum.iter()) {
+        if *norm >= normalization_floor {
+            *sample /= *norm;
+        } else {
+            *sample = 0.0;
+        }
+    }
+
+    output[pad..pad + samples.len()].to_vec()
+}
+
+/// Shifts the pitch of an audio buffer by `shift_ratio` (e.g. 2.0 = up one octave,
+/// 0.5 = down one octave) using an STFT phase vocoder
+///
+/// The output buffer is always the same length as the input, so callers pass the
+/// same `length` they supplied here to `free_pitch_shift_result_rust`.
+///
+/// # Safety
+/// * `buffer` must point to at least `length` valid `f32` samples
+/// * Caller MUST call `free_pitch_shift_result_rust` to deallocate the returned pointer
+///
+/// # Memory Management Pattern (Critical for FFI/JNI)
+/// * Rust allocates → Returns raw pointer → Swift/Kotlin copies → Swift/Kotlin frees Rust memory
+#[no_mangle]
+pub unsafe extern "C" fn pitch_shift_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+    shift_ratio: c_float,
+) -> *mut c_float {
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return std::ptr::null_mut();
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return std::ptr::null_mut();
+    }
+
+    if !(8000..=48000).contains(&sample_rate) {
+        eprintln!(
+            "[Rust FFI] Error: sample_rate must be in range [8000, 48000] Hz, got {sample_rate}"
+        );
+        return std::ptr::null_mut();
+    }
+
+    if shift_ratio <= 0.0 {
+        eprintln!("[Rust FFI] Error: shift_ratio must be > 0.0, got {shift_ratio}");
+        return std::ptr::null_mut();
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let shifted = phase_vocoder_pitch_shift(input_slice, shift_ratio);
+
+    Box::into_raw(shifted.into_boxed_slice()) as *mut c_float
+}
+
+/// Convenience pitch-shift mode that detects the input's current pitch and retunes it
+/// to the nearest equal-tempered semitone, auto-tune style
+///
+/// Reuses `detect_pitch_rust`'s underlying detection logic to find the source pitch,
+/// then derives `shift_ratio` from the ratio between the nearest MIDI note's frequency
+/// and the detected frequency. Silent or unvoiced input has no pitch to retune toward
+/// and is returned unchanged.
+///
+/// # Safety
+/// * `buffer` must point to at least `length` valid `f32` samples
+/// * Caller MUST call `free_pitch_shift_result_rust` to deallocate the returned pointer
+#[no_mangle]
+pub unsafe extern "C" fn retune_to_nearest_semitone_rust(
+    buffer: *const c_float,
+    length: c_int,
+    sample_rate: c_int,
+) -> *mut c_float {
+    if buffer.is_null() {
+        eprintln!("[Rust FFI] Error: buffer pointer is null");
+        return std::ptr::null_mut();
+    }
+
+    if length <= 0 {
+        eprintln!("[Rust FFI] Error: length must be > 0, got {length}");
+        return std::ptr::null_mut();
+    }
+
+    if !(8000..=48000).contains(&sample_rate) {
+        eprintln!(
+            "[Rust FFI] Error: sample_rate must be in range [8000, 48000] Hz, got {sample_rate}"
+        );
+        return std::ptr::null_mut();
+    }
+
+    let input_slice = slice::from_raw_parts(buffer, length as usize);
+    let pitch = detect_pitch_on_slice(input_slice, sample_rate as u32);
+
+    if !pitch.is_voiced || pitch.frequency <= 0.0 {
+        return Box::into_raw(input_slice.to_vec().into_boxed_slice()) as *mut c_float;
+    }
+
+    let target_frequency = midi_to_frequency(pitch.midi_note);
+    let shift_ratio = target_frequency / pitch.frequency;
+    let shifted = phase_vocoder_pitch_shift(input_slice, shift_ratio);
+
+    Box::into_raw(shifted.into_boxed_slice()) as *mut c_float
+}
+
+/// Frees a buffer returned by `pitch_shift_rust` or `retune_to_nearest_semitone_rust`
+///
+/// # Safety
+/// * Must only be called once per pointer returned from those functions
+/// * `length` must match the `length` originally passed to them (output length always
+///   equals input length)
+/// * Null pointers are handled gracefully and do nothing
+#[no_mangle]
+pub unsafe extern "C" fn free_pitch_shift_result_rust(ptr: *mut c_float, length: c_int) {
+    if ptr.is_null() {
+        return;
+    }
+
+    if length <= 0 {
+        eprintln!(
+            "[Rust FFI] Error: free_pitch_shift_result_rust called with invalid length {length}"
+        );
+        return;
+    }
+
+    let _ = Box::from_raw(slice::from_raw_parts_mut(ptr, length as usize));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_ffi_placeholder() {
+        assert_eq!(test_ffi_bridge(), 42);
+    }
+
+    #[test]
+    fn test_compute_fft_null_buffer() {
+        unsafe {
+            let result = compute_fft_rust(std::ptr::null(), 1024, 44100, 512, 0);
+            assert!(result.is_null(), "Should return null for null buffer");
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_invalid_length() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), 0, 44100, 512, 0);
+            assert!(result.is_null(), "Should return null for length <= 0");
+
+            let result = compute_fft_rust(buffer.as_ptr(), -10, 44100, 512, 0);
+            assert!(result.is_null(), "Should return null for negative length");
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_invalid_sample_rate() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, 0, 512, 0);
+            assert!(result.is_null(), "Should return null for sample_rate <= 0");
+
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, -100, 512, 0);
+            assert!(
                 result.is_null(),
                 "Should return null for negative sample_rate"
             );
@@ -377,434 +2334,1597 @@ mod tests {
     }
 
     #[test]
-    fn test_compute_fft_invalid_fft_size_not_power_of_2() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
+    fn test_compute_fft_invalid_fft_size_not_power_of_2() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+
+        unsafe {
+            // Test non-power-of-2 sizes
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 500, 0);
+            assert!(
+                result.is_null(),
+                "Should return null for non-power-of-2 FFT size"
+            );
+
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 1000, 0);
+            assert!(
+                result.is_null(),
+                "Should return null for non-power-of-2 FFT size"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_invalid_fft_size_out_of_range() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+
+        unsafe {
+            // Test below minimum (256)
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 128, 0);
+            assert!(result.is_null(), "Should return null for FFT size < 256");
+
+            // Test above maximum (8192)
+            let result = compute_fft_rust(buffer.as_ptr(), 16384, 44100, 16384, 0);
+            assert!(result.is_null(), "Should return null for FFT size > 8192");
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_valid_input_returns_non_null() {
+        // Generate a simple sine wave at 440 Hz
+        let sample_rate = 44100;
+        let frequency = 440.0;
+        let duration = 0.1; // 100ms
+        let num_samples = (sample_rate as f32 * duration) as usize;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * frequency * t).sin());
+        }
+
+        let fft_size = 2048;
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), num_samples as c_int, sample_rate, fft_size, 0);
+            assert!(!result.is_null(), "Should return valid pointer");
+
+            // Clean up memory (fft_size / 2 + 1)
+            free_fft_result_rust(result, (fft_size / 2) + 1);
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_result_length() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        let sample_rate = 44100;
+        let fft_size = 1024;
+        let expected_result_length = (fft_size / 2) + 1; // loqa-voice-dsp returns N/2 + 1
+
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), 2048, sample_rate, fft_size, 0);
+            assert!(!result.is_null());
+
+            // Verify we can read the result (this tests memory safety)
+            let result_slice = slice::from_raw_parts(result, expected_result_length as usize);
+            assert_eq!(result_slice.len(), expected_result_length as usize);
+
+            // All values should be finite (not NaN or Infinity)
+            for val in result_slice {
+                assert!(val.is_finite(), "FFT result should be finite");
+            }
+
+            // Clean up
+            free_fft_result_rust(result, expected_result_length);
+        }
+    }
+
+    #[test]
+    fn test_compute_fft_sine_wave_peak_detection() {
+        // Generate a pure sine wave at known frequency
+        let sample_rate = 44100;
+        let target_frequency = 1000.0; // 1 kHz
+        let fft_size = 4096;
+        let num_samples = fft_size;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * target_frequency * t).sin());
+        }
+
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), num_samples as c_int, sample_rate, fft_size as c_int, 0);
+            assert!(!result.is_null());
+
+            let magnitude_len = (fft_size / 2) + 1;
+            let magnitude_slice = slice::from_raw_parts(result, magnitude_len);
+
+            // Find the peak in the magnitude spectrum
+            let mut max_magnitude = 0.0_f32;
+            let mut max_index = 0;
+            for (i, &mag) in magnitude_slice.iter().enumerate() {
+                if mag > max_magnitude {
+                    max_magnitude = mag;
+                    max_index = i;
+                }
+            }
+
+            // Calculate the frequency of the peak
+            let peak_frequency = (max_index as f32) * (sample_rate as f32 / fft_size as f32);
+
+            // The peak should be close to our target frequency (within 1 bin)
+            let frequency_resolution = sample_rate as f32 / fft_size as f32;
+            let frequency_error = (peak_frequency - target_frequency).abs();
+
+            assert!(
+                frequency_error < frequency_resolution * 1.5,
+                "Peak frequency {peak_frequency} Hz should be close to target {target_frequency} Hz (error: {frequency_error} Hz)"
+            );
+
+            free_fft_result_rust(result, ((fft_size / 2) + 1) as c_int);
+        }
+    }
+
+    #[test]
+    fn test_free_fft_result_handles_null() {
+        // Should not crash
+        unsafe {
+            free_fft_result_rust(std::ptr::null_mut(), 256);
+        }
+    }
+
+    #[test]
+    fn test_free_fft_result_handles_invalid_length() {
+        let buffer: Vec<f32> = vec![0.5; 1024];
+        unsafe {
+            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 512, 0);
+            assert!(!result.is_null());
+
+            // These should handle gracefully (not crash)
+            free_fft_result_rust(result, 0);
+        }
+        // Note: We've now leaked the memory, but that's ok for this test
+        // In production, free should be called with correct length
+    }
+
+    #[test]
+    fn test_memory_safety_multiple_allocations() {
+        // Test that we can allocate and free multiple FFT results without issues
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        let sample_rate = 44100;
+        let fft_size = 1024;
+        let result_len = (fft_size / 2) + 1;
+
+        unsafe {
+            for _ in 0..10 {
+                let result = compute_fft_rust(buffer.as_ptr(), 2048, sample_rate, fft_size, 0);
+                assert!(!result.is_null());
+                free_fft_result_rust(result, result_len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_window_none_is_identity() {
+        let mut samples = vec![1.0_f32, 0.5, -0.5, -1.0];
+        let original = samples.clone();
+        apply_window(&mut samples, 0);
+        assert_eq!(samples, original, "window_type=0 should leave samples untouched");
+    }
+
+    #[test]
+    fn test_apply_window_hann_tapers_edges_to_zero() {
+        let mut samples = vec![1.0_f32; 8];
+        apply_window(&mut samples, 1);
+        assert!(
+            samples[0].abs() < 1e-6,
+            "Hann window should taper the first sample to ~0, got {}",
+            samples[0]
+        );
+        assert!(
+            samples[samples.len() - 1].abs() < 1e-6,
+            "Hann window should taper the last sample to ~0, got {}",
+            samples[samples.len() - 1]
+        );
+    }
+
+    #[test]
+    fn test_apply_window_unknown_type_falls_back_to_none() {
+        let mut samples = vec![1.0_f32, 0.5, -0.5, -1.0];
+        let original = samples.clone();
+        apply_window(&mut samples, 99);
+        assert_eq!(samples, original, "unknown window_type should behave like none");
+    }
+
+    #[test]
+    fn test_compute_fft_with_window_type_returns_non_null() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        let sample_rate = 44100;
+        let fft_size = 1024;
+        let result_len = (fft_size / 2) + 1;
+
+        for window_type in [0, 1, 2, 3] {
+            unsafe {
+                let result =
+                    compute_fft_rust(buffer.as_ptr(), 2048, sample_rate, fft_size, window_type);
+                assert!(!result.is_null(), "window_type {window_type} should succeed");
+                free_fft_result_rust(result, result_len);
+            }
+        }
+    }
+
+    // ======== Spectrum Bands Tests ========
+
+    #[test]
+    fn test_compute_spectrum_bands_null_buffer() {
+        let mut out_bands = vec![0.0_f32; 16];
+        unsafe {
+            let status = compute_spectrum_bands_rust(
+                std::ptr::null(),
+                1024,
+                44100,
+                1024,
+                16,
+                SPECTRUM_SCALING_LINEAR,
+                out_bands.as_mut_ptr(),
+            );
+            assert_eq!(status, -1, "Should return -1 for null buffer");
+        }
+    }
+
+    #[test]
+    fn test_compute_spectrum_bands_null_out_bands() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        unsafe {
+            let status = compute_spectrum_bands_rust(
+                buffer.as_ptr(),
+                2048,
+                44100,
+                1024,
+                16,
+                SPECTRUM_SCALING_LINEAR,
+                std::ptr::null_mut(),
+            );
+            assert_eq!(status, -1, "Should return -1 for null out_bands");
+        }
+    }
+
+    #[test]
+    fn test_compute_spectrum_bands_invalid_num_bands() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        let mut out_bands = vec![0.0_f32; 16];
+        unsafe {
+            let status = compute_spectrum_bands_rust(
+                buffer.as_ptr(),
+                2048,
+                44100,
+                1024,
+                0,
+                SPECTRUM_SCALING_LINEAR,
+                out_bands.as_mut_ptr(),
+            );
+            assert_eq!(status, -1, "Should return -1 for num_bands <= 0");
+        }
+    }
+
+    #[test]
+    fn test_compute_spectrum_bands_sine_wave_linear() {
+        let sample_rate = 44100;
+        let target_frequency = 1000.0;
+        let fft_size = 4096;
+        let num_samples = fft_size;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * target_frequency * t).sin());
+        }
+
+        let num_bands = 16;
+        let mut out_bands = vec![0.0_f32; num_bands];
+
+        unsafe {
+            let status = compute_spectrum_bands_rust(
+                buffer.as_ptr(),
+                num_samples as c_int,
+                sample_rate,
+                fft_size as c_int,
+                num_bands as c_int,
+                SPECTRUM_SCALING_LINEAR,
+                out_bands.as_mut_ptr(),
+            );
+            assert_eq!(status, 0);
+        }
+
+        assert!(out_bands.iter().all(|v| v.is_finite() && *v >= 0.0));
+        assert!(
+            out_bands.iter().cloned().fold(0.0_f32, f32::max) > 0.0,
+            "Expected at least one band to carry energy from the 1 kHz tone"
+        );
+    }
+
+    #[test]
+    fn test_compute_spectrum_bands_log_scaling_in_unit_range() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+        let num_bands = 8;
+        let mut out_bands = vec![0.0_f32; num_bands];
+
+        unsafe {
+            let status = compute_spectrum_bands_rust(
+                buffer.as_ptr(),
+                2048,
+                44100,
+                1024,
+                num_bands as c_int,
+                SPECTRUM_SCALING_LOG,
+                out_bands.as_mut_ptr(),
+            );
+            assert_eq!(status, 0);
+        }
+
+        for value in out_bands {
+            assert!(
+                (0.0..=1.0).contains(&value),
+                "Log-scaled band value {value} should be normalized to 0.0..1.0"
+            );
+        }
+    }
+
+    // ======== Spectrum Squelch Tests ========
+
+    #[test]
+    fn test_spectrum_squelch_create_invalid_arguments() {
+        unsafe {
+            assert!(spectrum_squelch_create_rust(0, 10.0, 100.0, 60.0).is_null());
+            assert!(spectrum_squelch_create_rust(16, 0.0, 100.0, 60.0).is_null());
+            assert!(spectrum_squelch_create_rust(16, 10.0, 0.0, 60.0).is_null());
+            assert!(spectrum_squelch_create_rust(16, 10.0, 100.0, 0.0).is_null());
+        }
+    }
+
+    #[test]
+    fn test_spectrum_squelch_gates_quiet_bands() {
+        unsafe {
+            let handle = spectrum_squelch_create_rust(4, 10.0, 50.0, 60.0);
+            assert!(!handle.is_null());
+
+            // Feed several frames of low-level noise; the smoothed level should
+            // settle under the squelch threshold and get zeroed.
+            let mut bands = vec![0.01_f32; 4];
+            for _ in 0..20 {
+                let mut frame = bands.clone();
+                let status = spectrum_squelch_apply_rust(handle, frame.as_mut_ptr(), 4, 0.1);
+                assert_eq!(status, 0);
+                bands = frame;
+            }
+
+            assert!(
+                bands.iter().all(|&v| v == 0.0),
+                "Persistent low-level bands should be squelched to 0.0, got {bands:?}"
+            );
+
+            spectrum_squelch_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_spectrum_squelch_passes_loud_bands() {
+        unsafe {
+            let handle = spectrum_squelch_create_rust(2, 10.0, 50.0, 60.0);
+            assert!(!handle.is_null());
+
+            let mut frame = vec![5.0_f32, 5.0_f32];
+            let status = spectrum_squelch_apply_rust(handle, frame.as_mut_ptr(), 2, 0.1);
+            assert_eq!(status, 0);
+
+            assert!(
+                frame.iter().all(|&v| v > 0.0),
+                "Loud bands should not be squelched, got {frame:?}"
+            );
+
+            spectrum_squelch_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_spectrum_squelch_apply_rejects_band_count_mismatch() {
+        unsafe {
+            let handle = spectrum_squelch_create_rust(4, 10.0, 50.0, 60.0);
+            assert!(!handle.is_null());
+
+            let mut frame = vec![1.0_f32, 1.0_f32];
+            let status = spectrum_squelch_apply_rust(handle, frame.as_mut_ptr(), 2, 0.1);
+            assert_eq!(status, -1, "Mismatched band count should fail");
+
+            spectrum_squelch_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_spectrum_squelch_destroy_handles_null() {
+        unsafe {
+            spectrum_squelch_destroy_rust(std::ptr::null_mut());
+        }
+    }
+
+    // ======== Pitch Detection Tests ========
+
+    #[test]
+    fn test_detect_pitch_null_buffer() {
+        unsafe {
+            let result = detect_pitch_rust(std::ptr::null(), 1024, 44100);
+            assert_eq!(result.frequency, 0.0, "Should return frequency=0.0 for null buffer");
+            assert_eq!(result.confidence, 0.0, "Should return confidence=0.0 for null buffer");
+            assert!(!result.is_voiced, "Should return is_voiced=false for null buffer");
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_invalid_length() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+        unsafe {
+            // Test zero length
+            let result = detect_pitch_rust(buffer.as_ptr(), 0, 44100);
+            assert_eq!(result.frequency, 0.0);
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+
+            // Test negative length
+            let result = detect_pitch_rust(buffer.as_ptr(), -10, 44100);
+            assert_eq!(result.frequency, 0.0);
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_invalid_sample_rate_below_minimum() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+        unsafe {
+            // Test below 8000 Hz (AC3)
+            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 7999);
+            assert_eq!(result.frequency, 0.0, "Should return error for sample rate < 8000 Hz");
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+
+            // Test zero sample rate
+            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 0);
+            assert_eq!(result.frequency, 0.0);
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+
+            // Test negative sample rate
+            let result = detect_pitch_rust(buffer.as_ptr(), 1024, -100);
+            assert_eq!(result.frequency, 0.0);
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_invalid_sample_rate_above_maximum() {
+        let buffer: Vec<f32> = vec![0.0; 1024];
+        unsafe {
+            // Test above 48000 Hz (AC3)
+            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 48001);
+            assert_eq!(result.frequency, 0.0, "Should return error for sample rate > 48000 Hz");
+            assert_eq!(result.confidence, 0.0);
+            assert!(!result.is_voiced);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_valid_sample_rates() {
+        let buffer: Vec<f32> = vec![0.5; 2048];
+
+        unsafe {
+            // Test minimum valid sample rate (8000 Hz)
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 8000);
+            // Should not error (frequency may be 0 due to buffer content, but call should succeed)
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+
+            // Test common sample rate (44100 Hz)
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 44100);
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+
+            // Test maximum valid sample rate (48000 Hz)
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 48000);
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_confidence_range() {
+        // Generate synthetic tone at 440 Hz
+        let sample_rate = 44100;
+        let frequency = 440.0;
+        let duration = 0.1; // 100ms
+        let num_samples = (sample_rate as f32 * duration) as usize;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * frequency * t).sin());
+        }
+
+        unsafe {
+            let result = detect_pitch_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
+
+            // AC5: Confidence must be in range [0.0, 1.0]
+            assert!(
+                result.confidence >= 0.0 && result.confidence <= 1.0,
+                "Confidence {:.3} must be in range [0.0, 1.0]",
+                result.confidence
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_sine_wave_220hz() {
+        // Generate a pure 220 Hz sine wave (A3) - within human voice range
+        let sample_rate = 44100;
+        let target_frequency = 220.0; // Within PITCH_MIN_FREQUENCY..PITCH_MAX_FREQUENCY range
+        let duration = 0.1; // 100ms should be enough for YIN
+        let num_samples = (sample_rate as f32 * duration) as usize;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * target_frequency * t).sin());
+        }
+
+        unsafe {
+            let result = detect_pitch_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
+
+            // For a clear sine wave within the detection range, we should detect a pitch
+            // YIN is very accurate for pure tones in the target frequency range
+            if result.is_voiced {
+                // If voiced, frequency should be close to 220 Hz
+                let error = (result.frequency - target_frequency).abs();
+                let error_percent = (error / target_frequency) * 100.0;
+
+                assert!(
+                    error_percent < 10.0,
+                    "Detected frequency {:.1} Hz should be within 10% of target {:.1} Hz (error: {:.2}%)",
+                    result.frequency,
+                    target_frequency,
+                    error_percent
+                );
+
+                // Confidence should be reasonably high for clean tone
+                assert!(
+                    result.confidence > 0.5,
+                    "Confidence {:.3} should be > 0.5 for clear sine wave",
+                    result.confidence
+                );
+
+                // 220 Hz is A3 (MIDI 57), roughly in tune
+                assert_eq!(result.midi_note, 57, "220 Hz should resolve to MIDI note 57 (A3)");
+                assert!(
+                    result.cents.abs() <= 50,
+                    "cents {} should be within [-50, 50]",
+                    result.cents
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_silence_has_zeroed_midi_note_and_cents() {
+        let buffer: Vec<f32> = vec![0.0; 2048];
+        unsafe {
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 44100);
+            assert!(!result.is_voiced);
+            assert_eq!(result.midi_note, 0);
+            assert_eq!(result.cents, 0);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_silence_returns_unvoiced() {
+        // Test with silence (all zeros)
+        let buffer: Vec<f32> = vec![0.0; 2048];
+        let sample_rate = 44100;
+
+        unsafe {
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, sample_rate);
+
+            // AC4: Silence should return frequency=0.0 and is_voiced=false
+            assert_eq!(
+                result.frequency, 0.0,
+                "Silence should return frequency=0.0"
+            );
+            assert!(
+                !result.is_voiced,
+                "Silence should be classified as unvoiced"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_noise_behavior() {
+        // Generate white noise (random values)
+        let mut buffer: Vec<f32> = vec![0.0; 2048];
+        let sample_rate = 44100;
+
+        // Simple pseudo-random noise generator
+        for (i, sample) in buffer.iter_mut().enumerate() {
+            // Use a simple hash-like function for reproducibility
+            let hash = (i as u32).wrapping_mul(2654435761);
+            *sample = ((hash % 1000) as f32 / 1000.0) * 2.0 - 1.0; // Range: [-1.0, 1.0]
+        }
+
+        unsafe {
+            let result = detect_pitch_rust(buffer.as_ptr(), 2048, sample_rate);
+
+            // Noise behavior: The YIN algorithm may detect spurious periodicities in noise
+            // The important thing is that confidence values are always in valid range
+            assert!(
+                result.confidence >= 0.0 && result.confidence <= 1.0,
+                "Confidence must be in valid range [0.0, 1.0], got {:.3}",
+                result.confidence
+            );
+
+            // AC4: If unvoiced, frequency should be 0.0
+            if !result.is_voiced {
+                assert_eq!(
+                    result.frequency, 0.0,
+                    "Unvoiced noise should have frequency=0.0"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_multiple_sample_rates() {
+        // Generate 220 Hz tone (A3)
+        let target_frequency = 220.0;
+
+        for sample_rate in [8000, 16000, 22050, 44100, 48000] {
+            let duration = 0.1;
+            let num_samples = (sample_rate as f32 * duration) as usize;
+
+            let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+            for i in 0..num_samples {
+                let t = i as f32 / sample_rate as f32;
+                buffer.push((2.0 * PI * target_frequency * t).sin());
+            }
+
+            unsafe {
+                let result = detect_pitch_rust(
+                    buffer.as_ptr(),
+                    num_samples as c_int,
+                    sample_rate as c_int
+                );
+
+                // AC3: All sample rates in 8000-48000 Hz should work
+                assert!(
+                    result.confidence >= 0.0 && result.confidence <= 1.0,
+                    "Sample rate {} Hz should work (got confidence {:.3})",
+                    sample_rate,
+                    result.confidence
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_result_struct_layout() {
+        // Verify PitchResult struct is properly laid out for FFI
+        // This is a compile-time check, but runtime verification doesn't hurt
+        let test_result = PitchResult {
+            frequency: 440.0,
+            confidence: 0.95,
+            is_voiced: true,
+            midi_note: 69,
+            cents: 0,
+        };
+
+        assert_eq!(test_result.frequency, 440.0);
+        assert_eq!(test_result.confidence, 0.95);
+        assert!(test_result.is_voiced);
+
+        // Verify struct is Copy (required for FFI)
+        let copied = test_result;
+        assert_eq!(copied.frequency, 440.0);
+        assert_eq!(test_result.frequency, 440.0); // Original still valid
+    }
+
+    // ======== Note Detection Tests ========
+
+    fn note_name_to_string(note_name: &[c_char; 4]) -> String {
+        note_name
+            .iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| c as u8 as char)
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_note_silence_is_zeroed() {
+        let buffer: Vec<f32> = vec![0.0; 2048];
+        unsafe {
+            let result = detect_note_rust(buffer.as_ptr(), 2048, 44100);
+            assert!(!result.is_voiced);
+            assert_eq!(result.cents, 0);
+            assert_eq!(note_name_to_string(&result.note_name), "");
+        }
+    }
+
+    #[test]
+    fn test_detect_note_a4_sine_wave() {
+        let sample_rate = 44100;
+        let frequency = 440.0; // A4
+        let duration = 0.1;
+        let num_samples = (sample_rate as f32 * duration) as usize;
+
+        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
+        for i in 0..num_samples {
+            let t = i as f32 / sample_rate as f32;
+            buffer.push((2.0 * PI * frequency * t).sin());
+        }
+
+        unsafe {
+            let result = detect_note_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
+
+            if result.is_voiced {
+                assert_eq!(
+                    note_name_to_string(&result.note_name),
+                    "A4",
+                    "440 Hz should resolve to A4"
+                );
+                assert!(
+                    result.cents.abs() <= 50,
+                    "cents {} should be within [-50, 50]",
+                    result.cents
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_midi_to_frequency_a4() {
+        let freq = midi_to_frequency(69);
+        assert!((freq - 440.0).abs() < 0.01, "MIDI 69 should be 440 Hz, got {freq}");
+    }
+
+    // ======== Streaming Analyzer Tests ========
+
+    #[test]
+    fn test_analyzer_create_invalid_arguments() {
+        unsafe {
+            assert!(analyzer_create_rust(0, 1024, 512).is_null());
+            assert!(analyzer_create_rust(44100, 500, 512).is_null());
+            assert!(analyzer_create_rust(44100, 1024, 0).is_null());
+            assert!(analyzer_create_rust(44100, 1024, 2048).is_null());
+        }
+    }
+
+    #[test]
+    fn test_analyzer_poll_before_enough_samples_not_ready() {
+        unsafe {
+            let handle = analyzer_create_rust(44100, 1024, 512);
+            assert!(!handle.is_null());
+
+            let buffer = vec![0.1_f32; 100];
+            let status = analyzer_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int);
+            assert_eq!(status, 0);
+
+            let mut out = vec![0.0_f32; (1024 / 2) + 1];
+            let poll_status = analyzer_poll_fft_rust(handle, out.as_mut_ptr());
+            assert_eq!(poll_status, 1, "Should not be ready with too few samples");
+
+            analyzer_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_analyzer_push_then_poll_produces_frame() {
+        unsafe {
+            let handle = analyzer_create_rust(44100, 1024, 512);
+            assert!(!handle.is_null());
+
+            let buffer = vec![0.5_f32; 1024];
+            let status = analyzer_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int);
+            assert_eq!(status, 0);
+
+            let mut out = vec![0.0_f32; (1024 / 2) + 1];
+            let poll_status = analyzer_poll_fft_rust(handle, out.as_mut_ptr());
+            assert_eq!(poll_status, 0, "Should have a frame ready after fft_size samples");
+            assert!(out.iter().all(|v| v.is_finite()));
+
+            // Draining again without pushing more samples should report not-ready.
+            let poll_again = analyzer_poll_fft_rust(handle, out.as_mut_ptr());
+            assert_eq!(poll_again, 1);
+
+            analyzer_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_analyzer_overlapping_frames_produce_multiple_polls() {
+        unsafe {
+            let handle = analyzer_create_rust(44100, 1024, 256);
+            assert!(!handle.is_null());
+
+            // Pushing enough samples for several overlapping hops beyond the first frame
+            // in a single call should queue every computed frame, not just the last one
+            // (a caller forwarding whatever-sized buffer its capture callback hands it,
+            // rather than hand-chunking to exactly hop_size, relies on this).
+            let buffer = vec![0.3_f32; 1024 + 256 * 3];
+            let status = analyzer_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int);
+            assert_eq!(status, 0);
+
+            let mut out = vec![0.0_f32; (1024 / 2) + 1];
+            let mut frames_ready = 0;
+            while analyzer_poll_fft_rust(handle, out.as_mut_ptr()) == 0 {
+                frames_ready += 1;
+                if frames_ready > 10 {
+                    break; // safety net against an infinite loop in a broken implementation
+                }
+            }
+
+            assert_eq!(
+                frames_ready, 4,
+                "Expected all 4 frames computed from one push to be queued and polled, got {frames_ready}"
+            );
+
+            analyzer_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_analyzer_pending_frame_queue_drops_oldest_past_bound() {
+        unsafe {
+            let fft_size = 256;
+            let hop_size = 32;
+            let handle = analyzer_create_rust(44100, fft_size, hop_size);
+            assert!(!handle.is_null());
+
+            // Enough samples for many more frames than ANALYZER_MAX_PENDING_FRAMES in a
+            // single push; the queue should cap out rather than grow without bound.
+            let extra_hops = ANALYZER_MAX_PENDING_FRAMES as i32 + 10;
+            let buffer = vec![0.3_f32; fft_size as usize + hop_size as usize * extra_hops as usize];
+            let status = analyzer_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int);
+            assert_eq!(status, 0);
+
+            let mut out = vec![0.0_f32; (fft_size as usize / 2) + 1];
+            let mut frames_ready = 0;
+            while analyzer_poll_fft_rust(handle, out.as_mut_ptr()) == 0 {
+                frames_ready += 1;
+                if frames_ready > (ANALYZER_MAX_PENDING_FRAMES + 10) {
+                    break; // safety net against an infinite loop in a broken implementation
+                }
+            }
+
+            assert_eq!(
+                frames_ready, ANALYZER_MAX_PENDING_FRAMES,
+                "Expected the pending frame queue to cap at ANALYZER_MAX_PENDING_FRAMES"
+            );
+
+            analyzer_destroy_rust(handle);
+        }
+    }
 
+    #[test]
+    fn test_analyzer_push_rejects_null_buffer() {
         unsafe {
-            // Test non-power-of-2 sizes
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 500);
-            assert!(
-                result.is_null(),
-                "Should return null for non-power-of-2 FFT size"
+            let handle = analyzer_create_rust(44100, 1024, 512);
+            assert!(!handle.is_null());
+
+            let status = analyzer_push_rust(handle, std::ptr::null(), 100);
+            assert_eq!(status, -1);
+
+            analyzer_destroy_rust(handle);
+        }
+    }
+
+    #[test]
+    fn test_analyzer_destroy_handles_null() {
+        unsafe {
+            analyzer_destroy_rust(std::ptr::null_mut());
+        }
+    }
+
+    // ======== Envelope / Compressor Tests ========
+
+    #[test]
+    fn test_compute_envelope_invalid_arguments() {
+        let buffer: Vec<f32> = vec![0.5; 1024];
+        let mut out_env = vec![0.0_f32; 1024];
+        unsafe {
+            assert_eq!(
+                compute_envelope_rust(std::ptr::null(), 1024, 44100, 10.0, 100.0, out_env.as_mut_ptr()),
+                -1
+            );
+            assert_eq!(
+                compute_envelope_rust(buffer.as_ptr(), 1024, 44100, 10.0, 100.0, std::ptr::null_mut()),
+                -1
+            );
+            assert_eq!(
+                compute_envelope_rust(buffer.as_ptr(), 0, 44100, 10.0, 100.0, out_env.as_mut_ptr()),
+                -1
+            );
+            assert_eq!(
+                compute_envelope_rust(buffer.as_ptr(), 1024, 44100, 0.0, 100.0, out_env.as_mut_ptr()),
+                -1
             );
+        }
+    }
 
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 1000);
-            assert!(
-                result.is_null(),
-                "Should return null for non-power-of-2 FFT size"
+    #[test]
+    fn test_compute_envelope_tracks_step_input() {
+        let mut buffer = vec![0.0_f32; 200];
+        for sample in buffer.iter_mut().skip(100) {
+            *sample = 1.0;
+        }
+        let mut out_env = vec![0.0_f32; buffer.len()];
+
+        unsafe {
+            let status = compute_envelope_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                44100,
+                5.0,
+                50.0,
+                out_env.as_mut_ptr(),
             );
+            assert_eq!(status, 0);
         }
+
+        assert!(out_env[0].abs() < 1e-6, "Envelope should start near 0");
+        assert!(
+            out_env[out_env.len() - 1] > out_env[105],
+            "Envelope should keep rising toward the step"
+        );
+        assert!(out_env.iter().all(|v| v.is_finite()));
     }
 
     #[test]
-    fn test_compute_fft_invalid_fft_size_out_of_range() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
+    fn test_apply_compression_invalid_arguments() {
+        let buffer: Vec<f32> = vec![0.5; 1024];
+        let mut out_buffer = vec![0.0_f32; 1024];
+        unsafe {
+            assert_eq!(
+                apply_compression_rust(
+                    buffer.as_ptr(),
+                    1024,
+                    44100,
+                    -20.0,
+                    0.5,
+                    10.0,
+                    100.0,
+                    0.0,
+                    out_buffer.as_mut_ptr(),
+                ),
+                -1,
+                "ratio < 1.0 should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_compression_attenuates_above_threshold() {
+        let buffer = vec![0.9_f32; 2048];
+        let mut out_buffer = vec![0.0_f32; buffer.len()];
 
         unsafe {
-            // Test below minimum (256)
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 128);
-            assert!(result.is_null(), "Should return null for FFT size < 256");
+            let status = apply_compression_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                44100,
+                -20.0,
+                4.0,
+                5.0,
+                50.0,
+                0.0,
+                out_buffer.as_mut_ptr(),
+            );
+            assert_eq!(status, 0);
+        }
 
-            // Test above maximum (8192)
-            let result = compute_fft_rust(buffer.as_ptr(), 16384, 44100, 16384);
-            assert!(result.is_null(), "Should return null for FFT size > 8192");
+        let last = out_buffer[out_buffer.len() - 1];
+        assert!(last.is_finite());
+        assert!(
+            last.abs() < buffer[0].abs(),
+            "A sustained loud signal above threshold should be attenuated, got {last}"
+        );
+    }
+
+    #[test]
+    fn test_apply_compression_unity_below_threshold() {
+        let buffer = vec![0.01_f32; 2048];
+        let mut out_buffer = vec![0.0_f32; buffer.len()];
+
+        unsafe {
+            let status = apply_compression_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                44100,
+                -6.0,
+                4.0,
+                5.0,
+                50.0,
+                0.0,
+                out_buffer.as_mut_ptr(),
+            );
+            assert_eq!(status, 0);
+        }
+
+        let last = out_buffer[out_buffer.len() - 1];
+        assert!(
+            (last - buffer[0]).abs() < 1e-3,
+            "A quiet signal below threshold should pass near-unity gain, got {last}"
+        );
+    }
+
+    // ======== BACF Pitch Detection Tests ========
+
+    #[test]
+    fn test_detect_pitch_bacf_null_buffer() {
+        unsafe {
+            let result = detect_pitch_bacf_rust(std::ptr::null(), 1024, 44100);
+            assert_eq!(result.frequency, 0.0);
+            assert!(!result.is_voiced);
         }
     }
 
     #[test]
-    fn test_compute_fft_valid_input_returns_non_null() {
-        // Generate a simple sine wave at 440 Hz
+    fn test_detect_pitch_bacf_too_short_buffer_is_unvoiced() {
+        let buffer: Vec<f32> = vec![0.5; 64];
+        unsafe {
+            let result = detect_pitch_bacf_rust(buffer.as_ptr(), 64, 44100);
+            assert!(!result.is_voiced, "A too-short buffer can't span 2 periods, should be unvoiced");
+            assert_eq!(result.frequency, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_bacf_silence_returns_unvoiced() {
+        let buffer: Vec<f32> = vec![0.0; 4096];
+        unsafe {
+            let result = detect_pitch_bacf_rust(buffer.as_ptr(), 4096, 44100);
+            assert!(!result.is_voiced);
+            assert_eq!(result.frequency, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_bacf_sine_wave_220hz() {
         let sample_rate = 44100;
-        let frequency = 440.0;
-        let duration = 0.1; // 100ms
+        let target_frequency = 220.0;
+        let duration = 0.2;
         let num_samples = (sample_rate as f32 * duration) as usize;
 
         let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
         for i in 0..num_samples {
             let t = i as f32 / sample_rate as f32;
-            buffer.push((2.0 * PI * frequency * t).sin());
+            buffer.push((2.0 * PI * target_frequency * t).sin());
         }
 
-        let fft_size = 2048;
         unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), num_samples as c_int, sample_rate, fft_size);
-            assert!(!result.is_null(), "Should return valid pointer");
+            let result = detect_pitch_bacf_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
 
-            // Clean up memory (fft_size / 2 + 1)
-            free_fft_result_rust(result, (fft_size / 2) + 1);
+            assert!(
+                result.confidence >= 0.0 && result.confidence <= 1.0,
+                "Confidence {:.3} must be in range [0.0, 1.0]",
+                result.confidence
+            );
+
+            if result.is_voiced {
+                let error_percent = ((result.frequency - target_frequency) / target_frequency).abs() * 100.0;
+                assert!(
+                    error_percent < 15.0,
+                    "BACF frequency {:.1} Hz should be within 15% of target {:.1} Hz",
+                    result.frequency,
+                    target_frequency
+                );
+            }
         }
     }
 
     #[test]
-    fn test_compute_fft_result_length() {
-        let buffer: Vec<f32> = vec![0.5; 2048];
+    fn test_detect_pitch_with_algorithm_dispatches() {
         let sample_rate = 44100;
-        let fft_size = 1024;
-        let expected_result_length = (fft_size / 2) + 1; // loqa-voice-dsp returns N/2 + 1
+        let buffer: Vec<f32> = vec![0.5; 4096];
 
         unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), 2048, sample_rate, fft_size);
-            assert!(!result.is_null());
+            let yin_result =
+                detect_pitch_with_algorithm_rust(buffer.as_ptr(), 4096, sample_rate, PITCH_ALGORITHM_YIN);
+            let direct_yin = detect_pitch_rust(buffer.as_ptr(), 4096, sample_rate);
+            assert_eq!(yin_result.frequency, direct_yin.frequency);
+
+            let bacf_result =
+                detect_pitch_with_algorithm_rust(buffer.as_ptr(), 4096, sample_rate, PITCH_ALGORITHM_BACF);
+            let direct_bacf = detect_pitch_bacf_rust(buffer.as_ptr(), 4096, sample_rate);
+            assert_eq!(bacf_result.frequency, direct_bacf.frequency);
+
+            // Unknown algorithm values fall back to YIN
+            let unknown_result = detect_pitch_with_algorithm_rust(buffer.as_ptr(), 4096, sample_rate, 99);
+            assert_eq!(unknown_result.frequency, direct_yin.frequency);
+        }
+    }
 
-            // Verify we can read the result (this tests memory safety)
-            let result_slice = slice::from_raw_parts(result, expected_result_length as usize);
-            assert_eq!(result_slice.len(), expected_result_length as usize);
+    // ======== Pitch Tracker Tests ========
 
-            // All values should be finite (not NaN or Infinity)
-            for val in result_slice {
-                assert!(val.is_finite(), "FFT result should be finite");
+    #[test]
+    fn test_pitch_tracker_create_invalid_arguments() {
+        unsafe {
+            assert!(pitch_tracker_create_rust(0, 1).is_null());
+            assert!(pitch_tracker_create_rust(5, 0).is_null());
+        }
+    }
+
+    fn sine_buffer(frequency: f32, sample_rate: c_int, duration_secs: f32) -> Vec<f32> {
+        let num_samples = (sample_rate as f32 * duration_secs) as usize;
+        (0..num_samples)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                (2.0 * PI * frequency * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pitch_tracker_smooths_and_destroys() {
+        unsafe {
+            let handle = pitch_tracker_create_rust(5, 1);
+            assert!(!handle.is_null());
+
+            let buffer = sine_buffer(220.0, 44100, 0.1);
+            let mut last = pitch_tracker_unvoiced_result();
+            for _ in 0..5 {
+                last = pitch_tracker_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int, 44100);
             }
 
-            // Clean up
-            free_fft_result_rust(result, expected_result_length);
+            if last.is_voiced {
+                let error_percent = ((last.frequency - 220.0) / 220.0).abs() * 100.0;
+                assert!(
+                    error_percent < 10.0,
+                    "Tracked frequency {:.1} Hz should stay near 220 Hz",
+                    last.frequency
+                );
+            }
+
+            pitch_tracker_destroy_rust(handle);
         }
     }
 
     #[test]
-    fn test_compute_fft_sine_wave_peak_detection() {
-        // Generate a pure sine wave at known frequency
-        let sample_rate = 44100;
-        let target_frequency = 1000.0; // 1 kHz
-        let fft_size = 4096;
-        let num_samples = fft_size;
+    fn test_pitch_tracker_decimation_reuses_last_result() {
+        unsafe {
+            let handle = pitch_tracker_create_rust(5, 3);
+            assert!(!handle.is_null());
 
-        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
-        for i in 0..num_samples {
-            let t = i as f32 / sample_rate as f32;
-            buffer.push((2.0 * PI * target_frequency * t).sin());
+            let voiced_buffer = sine_buffer(220.0, 44100, 0.1);
+            let silent_buffer = vec![0.0_f32; voiced_buffer.len()];
+
+            // Frame 0 runs detection against a voiced buffer and should track 220 Hz-ish.
+            let first =
+                pitch_tracker_push_rust(handle, voiced_buffer.as_ptr(), voiced_buffer.len() as c_int, 44100);
+
+            // Frames 1 and 2 are decimated away; even though they're silent, the
+            // decimated calls must return the cached result unchanged rather than
+            // reacting to the new (silent) input.
+            let second =
+                pitch_tracker_push_rust(handle, silent_buffer.as_ptr(), silent_buffer.len() as c_int, 44100);
+            let third =
+                pitch_tracker_push_rust(handle, silent_buffer.as_ptr(), silent_buffer.len() as c_int, 44100);
+
+            assert_eq!(second.frequency, first.frequency);
+            assert_eq!(third.frequency, first.frequency);
+
+            pitch_tracker_destroy_rust(handle);
         }
+    }
 
+    #[test]
+    fn test_pitch_tracker_reset_clears_window() {
         unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), num_samples as c_int, sample_rate, fft_size as c_int);
-            assert!(!result.is_null());
+            let handle = pitch_tracker_create_rust(5, 1);
+            assert!(!handle.is_null());
 
-            let magnitude_len = (fft_size / 2) + 1;
-            let magnitude_slice = slice::from_raw_parts(result, magnitude_len);
+            let buffer = sine_buffer(220.0, 44100, 0.1);
+            pitch_tracker_push_rust(handle, buffer.as_ptr(), buffer.len() as c_int, 44100);
 
-            // Find the peak in the magnitude spectrum
-            let mut max_magnitude = 0.0_f32;
-            let mut max_index = 0;
-            for (i, &mag) in magnitude_slice.iter().enumerate() {
-                if mag > max_magnitude {
-                    max_magnitude = mag;
-                    max_index = i;
-                }
-            }
+            pitch_tracker_reset_rust(handle);
 
-            // Calculate the frequency of the peak
-            let peak_frequency = (max_index as f32) * (sample_rate as f32 / fft_size as f32);
+            let silent_buffer = vec![0.0_f32; buffer.len()];
+            let result =
+                pitch_tracker_push_rust(handle, silent_buffer.as_ptr(), silent_buffer.len() as c_int, 44100);
+            assert!(!result.is_voiced, "Freshly reset tracker should report unvoiced on silence");
 
-            // The peak should be close to our target frequency (within 1 bin)
-            let frequency_resolution = sample_rate as f32 / fft_size as f32;
-            let frequency_error = (peak_frequency - target_frequency).abs();
+            pitch_tracker_destroy_rust(handle);
+        }
+    }
 
-            assert!(
-                frequency_error < frequency_resolution * 1.5,
-                "Peak frequency {peak_frequency} Hz should be close to target {target_frequency} Hz (error: {frequency_error} Hz)"
+    #[test]
+    fn test_correct_octave_snaps_half_and_double() {
+        assert!((correct_octave(110.0, 220.0) - 220.0).abs() < 1e-3, "Half should snap up an octave");
+        assert!((correct_octave(440.0, 220.0) - 220.0).abs() < 1e-3, "Double should snap down an octave");
+        assert!((correct_octave(225.0, 220.0) - 225.0).abs() < 1e-3, "In-range estimate should pass through");
+    }
+
+    #[test]
+    fn test_pitch_tracker_destroy_handles_null() {
+        unsafe {
+            pitch_tracker_destroy_rust(std::ptr::null_mut());
+        }
+    }
+
+    // ======== Biquad Conditioning Tests ========
+
+    #[test]
+    fn test_design_biquad_clamps_cutoff_near_nyquist() {
+        let sample_rate = 44100.0;
+        // A cutoff above Nyquist should not produce NaN/Inf coefficients.
+        let coeffs = design_biquad(FILTER_TYPE_LOWPASS, 40000.0, 0.707, sample_rate);
+        assert!(coeffs.b0.is_finite());
+        assert!(coeffs.b1.is_finite());
+        assert!(coeffs.b2.is_finite());
+        assert!(coeffs.a1.is_finite());
+        assert!(coeffs.a2.is_finite());
+    }
+
+    #[test]
+    fn test_apply_biquad_lowpass_attenuates_high_frequency() {
+        let sample_rate = 44100.0;
+        let coeffs = design_biquad(FILTER_TYPE_LOWPASS, 200.0, 0.707, sample_rate);
+
+        let high_freq_tone = sine_buffer(8000.0, sample_rate as c_int, 0.05);
+        let filtered = apply_biquad(&coeffs, &high_freq_tone);
+
+        let input_energy: f32 = high_freq_tone.iter().map(|v| v * v).sum();
+        let output_energy: f32 = filtered.iter().map(|v| v * v).sum();
+
+        assert!(filtered.iter().all(|v| v.is_finite()));
+        assert!(
+            output_energy < input_energy,
+            "Low-pass filter should attenuate an 8 kHz tone well above its 200 Hz cutoff"
+        );
+    }
+
+    #[test]
+    fn test_detect_pitch_filtered_null_buffer() {
+        unsafe {
+            let result = detect_pitch_filtered_rust(
+                std::ptr::null(),
+                1024,
+                44100,
+                FILTER_TYPE_BANDPASS,
+                220.0,
+                1.0,
+            );
+            assert_eq!(result.frequency, 0.0);
+            assert!(!result.is_voiced);
+        }
+    }
+
+    #[test]
+    fn test_detect_pitch_filtered_sine_wave_stays_finite() {
+        let sample_rate = 44100;
+        let buffer = sine_buffer(220.0, sample_rate, 0.1);
+
+        unsafe {
+            let result = detect_pitch_filtered_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                sample_rate,
+                FILTER_TYPE_BANDPASS,
+                220.0,
+                1.0,
             );
-
-            free_fft_result_rust(result, ((fft_size / 2) + 1) as c_int);
+            assert!(result.frequency.is_finite());
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
         }
     }
 
     #[test]
-    fn test_free_fft_result_handles_null() {
-        // Should not crash
+    fn test_detect_pitch_voice_band_sine_wave_stays_finite() {
+        let sample_rate = 44100;
+        let buffer = sine_buffer(220.0, sample_rate, 0.1);
+
         unsafe {
-            free_fft_result_rust(std::ptr::null_mut(), 256);
+            let result = detect_pitch_voice_band_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate);
+            assert!(result.frequency.is_finite());
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
         }
     }
 
-    #[test]
-    fn test_free_fft_result_handles_invalid_length() {
-        let buffer: Vec<f32> = vec![0.5; 1024];
-        unsafe {
-            let result = compute_fft_rust(buffer.as_ptr(), 1024, 44100, 512);
-            assert!(!result.is_null());
+    // ======== PitchConfig Tests ========
 
-            // These should handle gracefully (not crash)
-            free_fft_result_rust(result, 0);
-        }
-        // Note: We've now leaked the memory, but that's ok for this test
-        // In production, free should be called with correct length
+    #[test]
+    fn test_pitch_config_default_matches_pitch_frequency_range() {
+        let config = pitch_config_default_rust();
+        assert_eq!(config.min_frequency, PITCH_MIN_FREQUENCY);
+        assert_eq!(config.max_frequency, PITCH_MAX_FREQUENCY);
+        assert_eq!(config.power_threshold, 0.0);
+        assert_eq!(config.clarity_threshold, 0.0);
     }
 
     #[test]
-    fn test_memory_safety_multiple_allocations() {
-        // Test that we can allocate and free multiple FFT results without issues
-        let buffer: Vec<f32> = vec![0.5; 2048];
+    fn test_detect_pitch_with_default_config_matches_detect_pitch_rust() {
         let sample_rate = 44100;
-        let fft_size = 1024;
-        let result_len = (fft_size / 2) + 1;
+        let buffer = sine_buffer(220.0, sample_rate, 0.1);
 
         unsafe {
-            for _ in 0..10 {
-                let result = compute_fft_rust(buffer.as_ptr(), 2048, sample_rate, fft_size);
-                assert!(!result.is_null());
-                free_fft_result_rust(result, result_len);
-            }
+            let baseline = detect_pitch_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate);
+            let configured = detect_pitch_with_config_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                sample_rate,
+                pitch_config_default_rust(),
+            );
+            assert_eq!(baseline.frequency, configured.frequency);
+            assert_eq!(baseline.is_voiced, configured.is_voiced);
+            assert_eq!(baseline.midi_note, configured.midi_note);
+            assert_eq!(baseline.cents, configured.cents);
         }
     }
 
-    // ======== Pitch Detection Tests ========
-
     #[test]
-    fn test_detect_pitch_null_buffer() {
+    fn test_detect_pitch_with_config_power_threshold_rejects_quiet_signal() {
+        let sample_rate = 44100;
+        let buffer: Vec<f32> = sine_buffer(220.0, sample_rate, 0.1)
+            .iter()
+            .map(|s| s * 0.001)
+            .collect();
+        let mut config = pitch_config_default_rust();
+        config.power_threshold = 0.5;
+
         unsafe {
-            let result = detect_pitch_rust(std::ptr::null(), 1024, 44100);
-            assert_eq!(result.frequency, 0.0, "Should return frequency=0.0 for null buffer");
-            assert_eq!(result.confidence, 0.0, "Should return confidence=0.0 for null buffer");
-            assert!(!result.is_voiced, "Should return is_voiced=false for null buffer");
+            let result = detect_pitch_with_config_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                sample_rate,
+                config,
+            );
+            assert!(!result.is_voiced, "Quiet signal should fail the power threshold");
+            assert_eq!(result.frequency, 0.0);
         }
     }
 
     #[test]
-    fn test_detect_pitch_invalid_length() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
-        unsafe {
-            // Test zero length
-            let result = detect_pitch_rust(buffer.as_ptr(), 0, 44100);
-            assert_eq!(result.frequency, 0.0);
-            assert_eq!(result.confidence, 0.0);
-            assert!(!result.is_voiced);
+    fn test_detect_pitch_with_config_clarity_threshold_rejects_signal() {
+        let sample_rate = 44100;
+        let buffer = sine_buffer(220.0, sample_rate, 0.1);
+        let mut config = pitch_config_default_rust();
+        config.clarity_threshold = 1.1; // Unreachable confidence forces is_voiced=false
 
-            // Test negative length
-            let result = detect_pitch_rust(buffer.as_ptr(), -10, 44100);
+        unsafe {
+            let result = detect_pitch_with_config_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                sample_rate,
+                config,
+            );
+            assert!(!result.is_voiced, "Unreachable clarity threshold should reject every signal");
             assert_eq!(result.frequency, 0.0);
-            assert_eq!(result.confidence, 0.0);
-            assert!(!result.is_voiced);
         }
     }
 
     #[test]
-    fn test_detect_pitch_invalid_sample_rate_below_minimum() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
+    fn test_detect_pitch_with_config_custom_frequency_range() {
+        let sample_rate = 44100;
+        let buffer = sine_buffer(880.0, sample_rate, 0.1);
+        let mut config = pitch_config_default_rust();
+        config.min_frequency = 600.0;
+        config.max_frequency = 1000.0;
+
         unsafe {
-            // Test below 8000 Hz (AC3)
-            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 7999);
-            assert_eq!(result.frequency, 0.0, "Should return error for sample rate < 8000 Hz");
-            assert_eq!(result.confidence, 0.0);
-            assert!(!result.is_voiced);
+            let result = detect_pitch_with_config_rust(
+                buffer.as_ptr(),
+                buffer.len() as c_int,
+                sample_rate,
+                config,
+            );
+            assert!(result.frequency.is_finite());
+            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+        }
+    }
 
-            // Test zero sample rate
-            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 0);
+    #[test]
+    fn test_detect_pitch_with_config_null_buffer() {
+        unsafe {
+            let result = detect_pitch_with_config_rust(
+                std::ptr::null(),
+                1024,
+                44100,
+                pitch_config_default_rust(),
+            );
             assert_eq!(result.frequency, 0.0);
-            assert_eq!(result.confidence, 0.0);
             assert!(!result.is_voiced);
+        }
+    }
 
-            // Test negative sample rate
-            let result = detect_pitch_rust(buffer.as_ptr(), 1024, -100);
-            assert_eq!(result.frequency, 0.0);
-            assert_eq!(result.confidence, 0.0);
-            assert!(!result.is_voiced);
+    // ======== Phase Vocoder Pitch Shift Tests ========
+
+    #[test]
+    fn test_fft_in_place_round_trip_is_identity() {
+        let mut data: Vec<Complex32> = (0..64)
+            .map(|i| Complex32::new((i as f32 * 0.1).sin(), 0.0))
+            .collect();
+        let original = data.clone();
+
+        fft_in_place(&mut data, false);
+        fft_in_place(&mut data, true);
+
+        for (original, round_tripped) in original.iter().zip(data.iter()) {
+            assert!((original.re - round_tripped.re).abs() < 1e-3);
+            assert!((original.im - round_tripped.im).abs() < 1e-3);
         }
     }
 
     #[test]
-    fn test_detect_pitch_invalid_sample_rate_above_maximum() {
-        let buffer: Vec<f32> = vec![0.0; 1024];
+    fn test_pitch_shift_rust_null_buffer() {
         unsafe {
-            // Test above 48000 Hz (AC3)
-            let result = detect_pitch_rust(buffer.as_ptr(), 1024, 48001);
-            assert_eq!(result.frequency, 0.0, "Should return error for sample rate > 48000 Hz");
-            assert_eq!(result.confidence, 0.0);
-            assert!(!result.is_voiced);
+            let result = pitch_shift_rust(std::ptr::null(), 1024, 44100, 2.0);
+            assert!(result.is_null());
         }
     }
 
     #[test]
-    fn test_detect_pitch_valid_sample_rates() {
-        let buffer: Vec<f32> = vec![0.5; 2048];
-
+    fn test_pitch_shift_rust_invalid_shift_ratio() {
+        let buffer: Vec<f32> = vec![0.0; 4096];
         unsafe {
-            // Test minimum valid sample rate (8000 Hz)
-            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 8000);
-            // Should not error (frequency may be 0 due to buffer content, but call should succeed)
-            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
-
-            // Test common sample rate (44100 Hz)
-            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 44100);
-            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, 44100, 0.0);
+            assert!(result.is_null());
 
-            // Test maximum valid sample rate (48000 Hz)
-            let result = detect_pitch_rust(buffer.as_ptr(), 2048, 48000);
-            assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, 44100, -1.0);
+            assert!(result.is_null());
         }
     }
 
     #[test]
-    fn test_detect_pitch_confidence_range() {
-        // Generate synthetic tone at 440 Hz
+    fn test_pitch_shift_rust_output_same_length_and_finite() {
         let sample_rate = 44100;
-        let frequency = 440.0;
-        let duration = 0.1; // 100ms
-        let num_samples = (sample_rate as f32 * duration) as usize;
-
-        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
-        for i in 0..num_samples {
-            let t = i as f32 / sample_rate as f32;
-            buffer.push((2.0 * PI * frequency * t).sin());
-        }
+        let buffer = sine_buffer(220.0, sample_rate, 0.25);
 
         unsafe {
-            let result = detect_pitch_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate, 2.0);
+            assert!(!result.is_null());
 
-            // AC5: Confidence must be in range [0.0, 1.0]
-            assert!(
-                result.confidence >= 0.0 && result.confidence <= 1.0,
-                "Confidence {:.3} must be in range [0.0, 1.0]",
-                result.confidence
-            );
+            let output = slice::from_raw_parts(result, buffer.len());
+            assert!(output.iter().all(|s| s.is_finite()));
+
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
         }
     }
 
     #[test]
-    fn test_detect_pitch_sine_wave_220hz() {
-        // Generate a pure 220 Hz sine wave (A3) - within human voice range
+    fn test_pitch_shift_rust_short_input_returned_unchanged() {
         let sample_rate = 44100;
-        let target_frequency = 220.0; // Within MIN_FREQUENCY..MAX_FREQUENCY range
-        let duration = 0.1; // 100ms should be enough for YIN
-        let num_samples = (sample_rate as f32 * duration) as usize;
-
-        let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
-        for i in 0..num_samples {
-            let t = i as f32 / sample_rate as f32;
-            buffer.push((2.0 * PI * target_frequency * t).sin());
-        }
+        let buffer = sine_buffer(220.0, sample_rate, 0.01); // Shorter than one FFT frame
 
         unsafe {
-            let result = detect_pitch_rust(buffer.as_ptr(), num_samples as c_int, sample_rate);
-
-            // For a clear sine wave within the detection range, we should detect a pitch
-            // YIN is very accurate for pure tones in the target frequency range
-            if result.is_voiced {
-                // If voiced, frequency should be close to 220 Hz
-                let error = (result.frequency - target_frequency).abs();
-                let error_percent = (error / target_frequency) * 100.0;
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate, 1.5);
+            assert!(!result.is_null());
 
-                assert!(
-                    error_percent < 10.0,
-                    "Detected frequency {:.1} Hz should be within 10% of target {:.1} Hz (error: {:.2}%)",
-                    result.frequency,
-                    target_frequency,
-                    error_percent
-                );
+            let output = slice::from_raw_parts(result, buffer.len());
+            assert_eq!(output, buffer.as_slice());
 
-                // Confidence should be reasonably high for clean tone
-                assert!(
-                    result.confidence > 0.5,
-                    "Confidence {:.3} should be > 0.5 for clear sine wave",
-                    result.confidence
-                );
-            }
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
         }
     }
 
     #[test]
-    fn test_detect_pitch_silence_returns_unvoiced() {
-        // Test with silence (all zeros)
-        let buffer: Vec<f32> = vec![0.0; 2048];
+    fn test_pitch_shift_octave_up_doubles_detected_frequency() {
         let sample_rate = 44100;
+        let buffer = sine_buffer(220.0, sample_rate, 0.3);
 
         unsafe {
-            let result = detect_pitch_rust(buffer.as_ptr(), 2048, sample_rate);
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate, 2.0);
+            assert!(!result.is_null());
+            let output = slice::from_raw_parts(result, buffer.len()).to_vec();
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
 
-            // AC4: Silence should return frequency=0.0 and is_voiced=false
-            assert_eq!(
-                result.frequency, 0.0,
-                "Silence should return frequency=0.0"
-            );
+            // Edge padding keeps every returned sample fully overlap-add normalized, so
+            // pitch detection can run on the whole buffer, not just a "settled" remainder.
+            let detected = detect_pitch_rust(output.as_ptr(), output.len() as c_int, sample_rate);
             assert!(
-                !result.is_voiced,
-                "Silence should be classified as unvoiced"
+                detected.frequency > 350.0 && detected.frequency < 500.0,
+                "Expected ~440 Hz after octave-up shift of a 220 Hz tone, got {}",
+                detected.frequency
             );
         }
     }
 
     #[test]
-    fn test_detect_pitch_noise_behavior() {
-        // Generate white noise (random values)
-        let mut buffer: Vec<f32> = vec![0.0; 2048];
+    fn test_pitch_shift_output_amplitude_stays_bounded_across_entire_buffer() {
         let sample_rate = 44100;
+        let buffer = sine_buffer(330.0, sample_rate, 0.3);
+        let peak_input = buffer.iter().cloned().fold(0.0f32, |acc, s| acc.max(s.abs()));
 
-        // Simple pseudo-random noise generator
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            // Use a simple hash-like function for reproducibility
-            let hash = (i as u32).wrapping_mul(2654435761);
-            *sample = ((hash % 1000) as f32 / 1000.0) * 2.0 - 1.0; // Range: [-1.0, 1.0]
+        for &ratio in &[0.75f32, 1.5, 2.0] {
+            unsafe {
+                let result =
+                    pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate, ratio);
+                assert!(!result.is_null());
+
+                let output = slice::from_raw_parts(result, buffer.len());
+                let peak_output = output.iter().cloned().fold(0.0f32, |acc, s| acc.max(s.abs()));
+                assert!(
+                    peak_output <= peak_input * 4.0,
+                    "ratio {ratio}: output peak {peak_output} exceeds 4x input peak {peak_input} \
+                     (overlap-add edge normalization likely blew up)"
+                );
+
+                free_pitch_shift_result_rust(result, buffer.len() as c_int);
+            }
         }
+    }
+
+    #[test]
+    fn test_pitch_shift_short_buffer_is_not_mostly_silence() {
+        // Regression test: edge padding should let even a ~100ms call return fully
+        // reconstructed audio rather than muting the first/last fft_size-hop_size
+        // samples (each edge is roughly a third of a buffer this size at a 2048-sample FFT).
+        let sample_rate = 44100;
+        let buffer = sine_buffer(220.0, sample_rate, 0.1);
+        let peak_input = buffer.iter().cloned().fold(0.0f32, |acc, s| acc.max(s.abs()));
 
         unsafe {
-            let result = detect_pitch_rust(buffer.as_ptr(), 2048, sample_rate);
+            let result = pitch_shift_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate, 1.5);
+            assert!(!result.is_null());
+
+            let output = slice::from_raw_parts(result, buffer.len());
+            let near_silent = output
+                .iter()
+                .filter(|s| s.abs() < peak_input * 0.05)
+                .count();
+            let silent_fraction = near_silent as f32 / output.len() as f32;
 
-            // Noise behavior: The YIN algorithm may detect spurious periodicities in noise
-            // The important thing is that confidence values are always in valid range
             assert!(
-                result.confidence >= 0.0 && result.confidence <= 1.0,
-                "Confidence must be in valid range [0.0, 1.0], got {:.3}",
-                result.confidence
+                silent_fraction < 0.1,
+                "Expected a short pitch-shifted buffer to be mostly reconstructed audio, \
+                 but {:.0}% of samples were near-silent",
+                silent_fraction * 100.0
             );
 
-            // AC4: If unvoiced, frequency should be 0.0
-            if !result.is_voiced {
-                assert_eq!(
-                    result.frequency, 0.0,
-                    "Unvoiced noise should have frequency=0.0"
-                );
-            }
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
         }
     }
 
     #[test]
-    fn test_detect_pitch_multiple_sample_rates() {
-        // Generate 220 Hz tone (A3)
-        let target_frequency = 220.0;
+    fn test_retune_to_nearest_semitone_null_buffer() {
+        unsafe {
+            let result = retune_to_nearest_semitone_rust(std::ptr::null(), 1024, 44100);
+            assert!(result.is_null());
+        }
+    }
 
-        for sample_rate in [8000, 16000, 22050, 44100, 48000] {
-            let duration = 0.1;
-            let num_samples = (sample_rate as f32 * duration) as usize;
+    #[test]
+    fn test_retune_to_nearest_semitone_silence_returned_unchanged() {
+        let sample_rate = 44100;
+        let buffer: Vec<f32> = vec![0.0; 4096];
 
-            let mut buffer: Vec<f32> = Vec::with_capacity(num_samples);
-            for i in 0..num_samples {
-                let t = i as f32 / sample_rate as f32;
-                buffer.push((2.0 * PI * target_frequency * t).sin());
-            }
+        unsafe {
+            let result =
+                retune_to_nearest_semitone_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate);
+            assert!(!result.is_null());
 
-            unsafe {
-                let result = detect_pitch_rust(
-                    buffer.as_ptr(),
-                    num_samples as c_int,
-                    sample_rate as c_int
-                );
+            let output = slice::from_raw_parts(result, buffer.len());
+            assert_eq!(output, buffer.as_slice());
 
-                // AC3: All sample rates in 8000-48000 Hz should work
-                assert!(
-                    result.confidence >= 0.0 && result.confidence <= 1.0,
-                    "Sample rate {} Hz should work (got confidence {:.3})",
-                    sample_rate,
-                    result.confidence
-                );
-            }
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
         }
     }
 
     #[test]
-    fn test_detect_pitch_result_struct_layout() {
-        // Verify PitchResult struct is properly laid out for FFI
-        // This is a compile-time check, but runtime verification doesn't hurt
-        let test_result = PitchResult {
-            frequency: 440.0,
-            confidence: 0.95,
-            is_voiced: true,
-        };
+    fn test_retune_to_nearest_semitone_in_tune_signal_stays_finite() {
+        let sample_rate = 44100;
+        // A3 (220 Hz) is already dead-center on MIDI note 57, so the retune ratio should be
+        // close to 1.0 and the output should remain well-behaved.
+        let buffer = sine_buffer(midi_to_frequency(57), sample_rate, 0.3);
 
-        assert_eq!(test_result.frequency, 440.0);
-        assert_eq!(test_result.confidence, 0.95);
-        assert!(test_result.is_voiced);
+        unsafe {
+            let result =
+                retune_to_nearest_semitone_rust(buffer.as_ptr(), buffer.len() as c_int, sample_rate);
+            assert!(!result.is_null());
 
-        // Verify struct is Copy (required for FFI)
-        let copied = test_result;
-        assert_eq!(copied.frequency, 440.0);
-        assert_eq!(test_result.frequency, 440.0); // Original still valid
+            let output = slice::from_raw_parts(result, buffer.len());
+            assert!(output.iter().all(|s| s.is_finite()));
+
+            free_pitch_shift_result_rust(result, buffer.len() as c_int);
+        }
     }
 }